@@ -0,0 +1,79 @@
+use crate::cmd::{leave, listeners};
+use crate::i18n::{self, tr};
+use crate::settings;
+use serenity::{
+    client::Context,
+    model::id::{ChannelId, GuildId},
+    prelude::TypeMapKey,
+};
+use std::{collections::HashMap, sync::Arc, time::Duration};
+use tokio::{sync::Mutex, task::JoinHandle};
+
+/// Pending departures of guilds where the bot was left alone.
+pub struct AloneTimers;
+
+impl TypeMapKey for AloneTimers {
+    type Value = Arc<Mutex<HashMap<GuildId, JoinHandle<()>>>>;
+}
+
+/// Starts or cancels the departure timer depending on whether anyone is
+/// still listening, called on every voice state update.
+pub async fn check(ctx: &Context, guild_id: GuildId) {
+    let manager = songbird::get(ctx)
+        .await
+        .expect("Songbird Voice client placed in at initialisation.")
+        .clone();
+
+    let channel = match manager.get(guild_id) {
+        Some(handler_lock) => handler_lock.lock().await.current_channel(),
+        None => None,
+    };
+
+    let alone = match channel {
+        Some(channel) => listeners(ctx, ChannelId(channel.0)).await.is_empty(),
+        None => false,
+    };
+
+    let grace = Duration::from_secs(settings::get(ctx, guild_id).await.alone_timeout);
+
+    let data = ctx.data.read().await;
+    let mut timers = data
+        .get::<AloneTimers>()
+        .expect("AloneTimers placed in at initialisation.")
+        .lock()
+        .await;
+
+    if !alone {
+        if let Some(timer) = timers.remove(&guild_id) {
+            timer.abort();
+        }
+        return;
+    }
+
+    if timers.contains_key(&guild_id) {
+        return;
+    }
+
+    let ctx = ctx.clone();
+    timers.insert(
+        guild_id,
+        tokio::spawn(async move {
+            tokio::time::sleep(grace).await;
+            leave(&ctx, guild_id).await;
+        }),
+    );
+}
+
+async fn leave(ctx: &Context, guild_id: GuildId) {
+    {
+        let data = ctx.data.read().await;
+        data.get::<AloneTimers>()
+            .expect("AloneTimers placed in at initialisation.")
+            .lock()
+            .await
+            .remove(&guild_id);
+    }
+
+    let lang = i18n::guild_lang(ctx, guild_id).await;
+    leave::disconnect_saying(ctx, guild_id, tr(lang, "alone.left", &[])).await;
+}
@@ -0,0 +1,169 @@
+use crate::cmd::{check_msg, now_playing, now_playing_embed, ControlBtn, TrackInfo};
+use crate::i18n::Lang;
+use crate::settings::{self, Announcements, NowPlayingMode};
+use serenity::{
+    client::Context,
+    model::channel::Message,
+    model::id::{ChannelId, GuildId, MessageId},
+    prelude::TypeMapKey,
+};
+use songbird::input::Metadata;
+use std::{collections::HashMap, sync::Arc, time::Duration};
+use tokio::sync::RwLock;
+
+/// Text channel where `/join` was last run, per guild.
+pub struct Announce;
+
+impl TypeMapKey for Announce {
+    type Value = Arc<RwLock<HashMap<GuildId, ChannelId>>>;
+}
+
+/// Last now playing message posted in each guild.
+pub struct NowPlaying;
+
+impl TypeMapKey for NowPlaying {
+    type Value = Arc<RwLock<HashMap<GuildId, (ChannelId, MessageId)>>>;
+}
+
+pub async fn set(ctx: &Context, guild_id: GuildId, chan_id: ChannelId) {
+    let data = ctx.data.read().await;
+    data.get::<Announce>()
+        .expect("Announce placed in at initialisation.")
+        .write()
+        .await
+        .insert(guild_id, chan_id);
+}
+
+/// Text channel where `/join` was last run, whatever the guild's settings.
+pub async fn invoked(ctx: &Context, guild_id: GuildId) -> Option<ChannelId> {
+    let data = ctx.data.read().await;
+    let channels = data
+        .get::<Announce>()
+        .expect("Announce placed in at initialisation.")
+        .read()
+        .await;
+
+    channels.get(&guild_id).copied()
+}
+
+/// Where a guild's announcements go, `invoked` being where they would by
+/// default. Nowhere when the guild turned them off.
+pub async fn target(
+    ctx: &Context,
+    guild_id: GuildId,
+    invoked: Option<ChannelId>,
+) -> Option<ChannelId> {
+    match settings::get(ctx, guild_id).await.announcements {
+        Announcements::Invoked => invoked,
+        Announcements::Channel(chan_id) => Some(ChannelId(chan_id)),
+        // Voice channels have their own text chat.
+        Announcements::Voice => {
+            let manager = songbird::get(ctx)
+                .await
+                .expect("Songbird Voice client placed in at initialisation.")
+                .clone();
+
+            let handler_lock = manager.get(guild_id)?;
+            let channel = handler_lock.lock().await.current_channel();
+            channel.map(|c| ChannelId(c.0))
+        }
+        Announcements::Off => None,
+    }
+}
+
+/// Where a guild's announcements go, if anywhere.
+pub async fn channel(ctx: &Context, guild_id: GuildId) -> Option<ChannelId> {
+    let invoked = invoked(ctx, guild_id).await;
+    target(ctx, guild_id, invoked).await
+}
+
+pub async fn say(ctx: &Context, guild_id: GuildId, content: impl std::fmt::Display) {
+    if let Some(chan_id) = channel(ctx, guild_id).await {
+        check_msg(chan_id.say(&ctx.http, content).await);
+    }
+}
+
+/// Shows what started playing where the guild's announcements go, either in a
+/// new message or in place of the last one depending on the guild's mode.
+/// Returns the message showing it.
+pub async fn show_now_playing(
+    ctx: &Context,
+    guild_id: GuildId,
+    np: &Metadata,
+    info: TrackInfo,
+    lang: Lang,
+) -> Option<Message> {
+    let chan_id = channel(ctx, guild_id).await?;
+    let mode = settings::get(ctx, guild_id).await.now_playing;
+
+    let previous = {
+        let data = ctx.data.read().await;
+        let messages = data
+            .get::<NowPlaying>()
+            .expect("NowPlaying placed in at initialisation.")
+            .read()
+            .await;
+
+        messages.get(&guild_id).copied()
+    };
+
+    let edited = match (mode, previous) {
+        // Only worth editing where announcements still go.
+        (NowPlayingMode::Edit, Some((previous_chan, message_id))) if previous_chan == chan_id => {
+            chan_id
+                .edit_message(&ctx.http, message_id, |m| {
+                    m.embed(|e| now_playing(e, np, info, Duration::default(), lang))
+                        .components(|c| c.add_action_row(ControlBtn::action_row(lang)))
+                })
+                .await
+                .ok()
+        }
+        (NowPlayingMode::Replace, Some((previous_chan, message_id))) => {
+            if let Err(why) = previous_chan.delete_message(&ctx.http, message_id).await {
+                println!("Err deleting now playing message: {:?}", why);
+            }
+            None
+        }
+        _ => None,
+    };
+
+    // Editing fails once someone deleted the message.
+    let message = match edited {
+        Some(message) => message,
+        None => {
+            let sent = chan_id
+                .send_message(&ctx.http, |m| {
+                    now_playing_embed(m, np.clone(), info, lang);
+                    m
+                })
+                .await;
+            match sent {
+                Ok(message) => message,
+                Err(why) => {
+                    println!("Error sending message: {:?}", why);
+                    return None;
+                }
+            }
+        }
+    };
+
+    let data = ctx.data.read().await;
+    data.get::<NowPlaying>()
+        .expect("NowPlaying placed in at initialisation.")
+        .write()
+        .await
+        .insert(guild_id, (chan_id, message.id));
+
+    Some(message)
+}
+
+/// Makes the next now playing message a new one, for a new session not to
+/// edit a message long buried in the channel.
+pub async fn forget_now_playing(ctx: &Context, guild_id: GuildId) {
+    let data = ctx.data.read().await;
+    data.get::<NowPlaying>()
+        .expect("NowPlaying placed in at initialisation.")
+        .write()
+        .await
+        .remove(&guild_id);
+}
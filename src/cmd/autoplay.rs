@@ -0,0 +1,84 @@
+use crate::cmd::{check_msg, defer_interaction, Res};
+use crate::history::Played;
+use crate::i18n::{self, tr};
+use crate::{settings, ytdl};
+use serenity::{
+    client::Context,
+    model::interactions::application_command::{
+        ApplicationCommandInteraction, ApplicationCommandInteractionDataOptionValue,
+    },
+    prelude::Mentionable,
+};
+
+pub async fn autoplay(ctx: &Context, cmd: &ApplicationCommandInteraction) -> Res {
+    check_msg(
+        cmd.create_interaction_response(&ctx.http, |response| {
+            defer_interaction(response, None, false)
+        })
+        .await,
+    );
+
+    let lang = i18n::lang(ctx, cmd.guild_id, &cmd.locale).await;
+    let enabled = match cmd.data.options.first().and_then(|o| o.resolved.as_ref()) {
+        Some(ApplicationCommandInteractionDataOptionValue::Boolean(enabled)) => *enabled,
+        _ => {
+            check_msg(
+                cmd.edit_original_interaction_response(&ctx.http, |response| {
+                    response.content(tr(lang, "autoplay.no_value", &[]))
+                })
+                .await,
+            );
+            return Ok(());
+        }
+    };
+
+    settings::update(ctx, cmd.guild_id.unwrap(), |s| s.autoplay = enabled).await;
+
+    let content = match enabled {
+        true => tr(lang, "autoplay.enabled", &[&cmd.user.mention()]),
+        false => tr(lang, "autoplay.disabled", &[&cmd.user.mention()]),
+    };
+
+    check_msg(
+        cmd.edit_original_interaction_response(&ctx.http, |response| response.content(content))
+            .await,
+    );
+
+    Ok(())
+}
+
+/// Looks up a track related to `url` using YouTube's mix for it, skipping
+/// anything found in `history`.
+pub async fn related_track(url: &str, history: &[Played]) -> Option<String> {
+    let id = youtube_id(url)?;
+    let mix_url = format!("https://www.youtube.com/watch?v={}&list=RD{}", id, id);
+
+    ytdl::flat_playlist(&mix_url, 25)
+        .await
+        .into_iter()
+        .filter(|entry| entry.id != id)
+        .filter(|entry| {
+            !history
+                .iter()
+                .any(|p| youtube_id(&p.url) == Some(entry.id.as_str()))
+        })
+        .map(|entry| entry.url())
+        .next()
+}
+
+fn youtube_id(url: &str) -> Option<&str> {
+    let id = if let Some((_, rest)) = url.split_once("youtu.be/") {
+        rest
+    } else {
+        let (_, query) = url.split_once('?')?;
+        query
+            .split('&')
+            .find_map(|param| param.strip_prefix("v="))?
+    };
+
+    let id = id.split(['?', '&', '#']).next()?;
+    match id.is_empty() {
+        true => None,
+        false => Some(id),
+    }
+}
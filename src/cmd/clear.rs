@@ -0,0 +1,18 @@
+use crate::cmd::{check_msg, defer_interaction, Invocation, Res};
+use crate::dj::Access;
+use crate::vote::{self, Motion};
+use serenity::client::Context;
+
+pub async fn clear(ctx: &Context, cmd: &Invocation<'_>, access: Access) -> Res {
+    check_msg(
+        cmd.create_interaction_response(&ctx.http, |response| {
+            defer_interaction(response, None, false)
+        })
+        .await,
+    );
+
+    match access {
+        Access::Dj => vote::bypass(ctx, cmd, Motion::Clear, "vote.by_dj").await,
+        Access::Open | Access::Vote => vote::hold(ctx, cmd, Motion::Clear).await,
+    }
+}
@@ -0,0 +1,318 @@
+use crate::cmd::{check_msg, defer_interaction, Registered, Res};
+use crate::i18n::{self, tr, Lang};
+use crate::settings::{self, Announcements, NowPlayingMode, Privileged, RateLimit, VoteRule};
+use serenity::{
+    client::Context,
+    model::channel::ChannelType,
+    model::id::RoleId,
+    model::interactions::application_command::{
+        ApplicationCommandInteraction, ApplicationCommandInteractionDataOptionValue,
+    },
+    prelude::Mentionable,
+};
+use std::{collections::HashMap, time::Duration};
+
+/// Longest jingle length that can be configured, in seconds.
+pub const MAX_JINGLE_LENGTH: i32 = 15;
+/// Bounds of how long votes can be configured to last, in seconds.
+pub const MIN_VOTE_DURATION: i32 = 5;
+pub const MAX_VOTE_DURATION: i32 = 120;
+
+pub async fn config(ctx: &Context, cmd: &ApplicationCommandInteraction) -> Res {
+    check_msg(
+        cmd.create_interaction_response(&ctx.http, |response| {
+            defer_interaction(response, None, true)
+        })
+        .await,
+    );
+
+    let guild_id = cmd.guild_id.unwrap();
+    let lang = i18n::lang(ctx, cmd.guild_id, &cmd.locale).await;
+    let sub = cmd.data.options.first().expect("Expected subcommand");
+    let value = sub.options.first().and_then(|o| o.resolved.as_ref());
+
+    let content = match (sub.name.as_str(), value) {
+        ("language", Some(ApplicationCommandInteractionDataOptionValue::String(code))) => {
+            let language = Lang::ALL.into_iter().find(|l| l.code() == code);
+            settings::update(ctx, guild_id, |s| s.language = language).await;
+            match language {
+                Some(lang) => tr(lang, "config.language", &[&tr(lang, "lang.name", &[])]),
+                None => {
+                    let lang = Lang::from_locale(&cmd.locale).unwrap_or(Lang::En);
+                    tr(lang, "config.language_auto", &[])
+                }
+            }
+        }
+        ("alone_timeout", Some(ApplicationCommandInteractionDataOptionValue::Integer(seconds))) => {
+            let seconds = (*seconds).max(0) as u64;
+            settings::update(ctx, guild_id, |s| s.alone_timeout = seconds).await;
+            tr(
+                lang,
+                "config.alone_timeout",
+                &[&humantime::format_duration(Duration::from_secs(seconds))],
+            )
+        }
+        ("announcements", _) => {
+            let option = |name: &str| {
+                sub.options
+                    .iter()
+                    .find(|o| o.name == name)
+                    .and_then(|o| o.resolved.as_ref())
+            };
+            let mode = match option("mode") {
+                Some(ApplicationCommandInteractionDataOptionValue::String(mode)) => mode.as_str(),
+                _ => "",
+            };
+
+            let announcements = match (mode, option("channel")) {
+                (
+                    "channel",
+                    Some(ApplicationCommandInteractionDataOptionValue::Channel(channel)),
+                ) => match channel.kind {
+                    ChannelType::Text
+                    | ChannelType::News
+                    | ChannelType::Voice
+                    | ChannelType::Stage => Some(Announcements::Channel(channel.id.0)),
+                    _ => None,
+                },
+                ("channel", _) => None,
+                ("voice", _) => Some(Announcements::Voice),
+                ("off", _) => Some(Announcements::Off),
+                _ => Some(Announcements::Invoked),
+            };
+
+            match announcements {
+                Some(announcements) => {
+                    settings::update(ctx, guild_id, |s| s.announcements = announcements).await;
+                    tr(
+                        lang,
+                        "config.announcements",
+                        &[&announcements.describe(lang)],
+                    )
+                }
+                None => tr(lang, "config.announcements_channel", &[]),
+            }
+        }
+        ("now_playing", Some(ApplicationCommandInteractionDataOptionValue::String(mode))) => {
+            let now_playing = match mode.as_str() {
+                "edit" => NowPlayingMode::Edit,
+                "replace" => NowPlayingMode::Replace,
+                _ => NowPlayingMode::New,
+            };
+            settings::update(ctx, guild_id, |s| s.now_playing = now_playing).await;
+            tr(lang, "config.now_playing", &[&now_playing.describe(lang)])
+        }
+        ("idle_timeout", Some(ApplicationCommandInteractionDataOptionValue::Integer(minutes))) => {
+            let minutes = (*minutes).max(0) as u64;
+            settings::update(ctx, guild_id, |s| s.idle_timeout = minutes).await;
+            match minutes {
+                0 => tr(lang, "config.idle_off", &[]),
+                m => tr(lang, "config.idle_timeout", &[&m]),
+            }
+        }
+        (
+            "spoken_announcements",
+            Some(ApplicationCommandInteractionDataOptionValue::Boolean(enabled)),
+        ) => {
+            let enabled = *enabled;
+            settings::update(ctx, guild_id, |s| s.spoken_announcements = enabled).await;
+            match enabled {
+                true => tr(lang, "config.spoken_on", &[]),
+                false => tr(lang, "config.spoken_off", &[]),
+            }
+        }
+        ("jingles", Some(ApplicationCommandInteractionDataOptionValue::Boolean(enabled))) => {
+            let enabled = *enabled;
+            settings::update(ctx, guild_id, |s| s.jingles = enabled).await;
+            match enabled {
+                true => tr(lang, "config.jingles_on", &[]),
+                false => tr(lang, "config.jingles_off", &[]),
+            }
+        }
+        ("jingle_length", Some(ApplicationCommandInteractionDataOptionValue::Integer(seconds))) => {
+            let seconds = (*seconds).clamp(1, MAX_JINGLE_LENGTH as i64) as u64;
+            settings::update(ctx, guild_id, |s| s.jingle_length = seconds).await;
+            tr(lang, "config.jingle_length", &[&seconds])
+        }
+        ("jingle_volume", Some(ApplicationCommandInteractionDataOptionValue::Integer(percent))) => {
+            let percent = (*percent).clamp(0, 100) as u64;
+            settings::update(ctx, guild_id, |s| s.jingle_volume = percent).await;
+            tr(lang, "config.jingle_volume", &[&percent])
+        }
+        ("vote_rule", _) => {
+            let option = |name: &str| {
+                sub.options
+                    .iter()
+                    .find(|o| o.name == name)
+                    .and_then(|o| o.resolved.as_ref())
+            };
+            let rule = match option("rule") {
+                Some(ApplicationCommandInteractionDataOptionValue::String(rule)) => rule.as_str(),
+                _ => "",
+            };
+            let value = match option("value") {
+                Some(ApplicationCommandInteractionDataOptionValue::Integer(v)) => Some(*v),
+                _ => None,
+            };
+
+            let rule = match (rule, value) {
+                ("majority", _) => Some(VoteRule::Majority),
+                ("percent", Some(p)) => Some(VoteRule::Percent(p.clamp(1, 100) as u64)),
+                ("count", Some(n)) => Some(VoteRule::Count(n.max(1) as u64)),
+                _ => None,
+            };
+
+            match rule {
+                Some(rule) => {
+                    settings::update(ctx, guild_id, |s| s.vote_rule = rule).await;
+                    tr(lang, "config.vote_rule", &[&rule.describe(lang)])
+                }
+                None => tr(lang, "config.vote_rule_value", &[]),
+            }
+        }
+        ("vote_duration", Some(ApplicationCommandInteractionDataOptionValue::Integer(seconds))) => {
+            let seconds =
+                (*seconds).clamp(MIN_VOTE_DURATION as i64, MAX_VOTE_DURATION as i64) as u64;
+            settings::update(ctx, guild_id, |s| s.vote_duration = seconds).await;
+            tr(lang, "config.vote_duration", &[&seconds])
+        }
+        ("dj_role", Some(ApplicationCommandInteractionDataOptionValue::Role(role))) => {
+            let role_id = role.id;
+            let mut added = false;
+            settings::update(ctx, guild_id, |s| {
+                added = !s.dj_roles.contains(&role_id.0);
+                match added {
+                    true => s.dj_roles.push(role_id.0),
+                    false => s.dj_roles.retain(|r| *r != role_id.0),
+                }
+            })
+            .await;
+            match added {
+                true => tr(lang, "config.dj_added", &[&role_id.mention()]),
+                false => tr(lang, "config.dj_removed", &[&role_id.mention()]),
+            }
+        }
+        ("privileged", Some(ApplicationCommandInteractionDataOptionValue::String(mode))) => {
+            let mode = match mode.as_str() {
+                "refuse" => Privileged::Refuse,
+                _ => Privileged::Vote,
+            };
+            settings::update(ctx, guild_id, |s| s.privileged = mode).await;
+            tr(lang, "config.privileged", &[&mode.describe(lang)])
+        }
+        ("rate_limit", _) => {
+            let option = |name: &str| {
+                sub.options
+                    .iter()
+                    .find(|o| o.name == name)
+                    .and_then(|o| o.resolved.as_ref())
+            };
+            let command = match option("command") {
+                Some(ApplicationCommandInteractionDataOptionValue::String(c)) => {
+                    c.trim().trim_start_matches('/').to_string()
+                }
+                _ => String::new(),
+            };
+            let (uses, per) = match (option("uses"), option("seconds")) {
+                (
+                    Some(ApplicationCommandInteractionDataOptionValue::Integer(uses)),
+                    Some(ApplicationCommandInteractionDataOptionValue::Integer(per)),
+                ) => ((*uses).max(0) as u64, (*per).max(1) as u64),
+                (Some(ApplicationCommandInteractionDataOptionValue::Integer(uses)), None) => {
+                    ((*uses).max(0) as u64, 60)
+                }
+                _ => (0, 60),
+            };
+
+            let known = ctx
+                .data
+                .read()
+                .await
+                .get::<Registered>()
+                .is_some_and(|commands| commands.contains(&command));
+            // Limits on commands that no longer exist can still be lifted.
+            let known = known
+                || uses == 0
+                    && settings::get(ctx, guild_id)
+                        .await
+                        .rate_limits
+                        .contains_key(&command);
+
+            if !known {
+                tr(lang, "config.rate_limit_unknown", &[&command])
+            } else if uses == 0 {
+                settings::update(ctx, guild_id, |s| {
+                    s.rate_limits.remove(&command);
+                })
+                .await;
+                tr(lang, "config.rate_limit_lifted", &[&command])
+            } else {
+                settings::update(ctx, guild_id, |s| {
+                    s.rate_limits
+                        .insert(command.clone(), RateLimit { uses, per });
+                })
+                .await;
+                tr(lang, "config.rate_limit", &[&command, &uses, &per])
+            }
+        }
+        _ => {
+            let s = settings::get(ctx, guild_id).await;
+            let yes_no = |b: bool| tr(lang, if b { "yes" } else { "no" }, &[]);
+            tr(
+                lang,
+                "config.show",
+                &[
+                    &match s.language {
+                        Some(l) => tr(l, "lang.name", &[]),
+                        None => tr(lang, "config.language_auto_name", &[]),
+                    },
+                    &yes_no(s.autoplay),
+                    &s.announcements.describe(lang),
+                    &s.now_playing.describe(lang),
+                    &humantime::format_duration(Duration::from_secs(s.alone_timeout)),
+                    &match s.idle_timeout {
+                        0 => tr(lang, "off", &[]),
+                        m => tr(lang, "config.minutes", &[&m]),
+                    },
+                    &yes_no(s.spoken_announcements),
+                    &yes_no(s.jingles),
+                    &s.jingle_length,
+                    &s.jingle_volume,
+                    &s.vote_rule.describe(lang),
+                    &s.vote_duration,
+                    &match s.dj_roles.is_empty() {
+                        true => tr(lang, "none", &[]),
+                        false => s
+                            .dj_roles
+                            .iter()
+                            .map(|r| RoleId(*r).mention().to_string())
+                            .collect::<Vec<String>>()
+                            .join(", "),
+                    },
+                    &s.privileged.describe(lang),
+                    &rate_limits(&s.rate_limits, lang),
+                ],
+            )
+        }
+    };
+
+    check_msg(
+        cmd.edit_original_interaction_response(&ctx.http, |response| response.content(content))
+            .await,
+    );
+
+    Ok(())
+}
+
+fn rate_limits(limits: &HashMap<String, RateLimit>, lang: Lang) -> String {
+    let mut limits = limits
+        .iter()
+        .map(|(c, l)| tr(lang, "config.rate_limit_entry", &[c, &l.uses, &l.per]))
+        .collect::<Vec<String>>();
+    limits.sort();
+
+    match limits.is_empty() {
+        true => tr(lang, "none", &[]),
+        false => limits.join(", "),
+    }
+}
@@ -0,0 +1,183 @@
+use crate::announce;
+use crate::cmd::join::join_channel;
+use crate::cmd::{check_msg, defer_interaction, voice_channel_of, Res};
+use crate::i18n::{self, tr};
+use serenity::{
+    client::Context,
+    model::id::{ChannelId, GuildId, UserId},
+    model::interactions::application_command::{
+        ApplicationCommandInteraction, ApplicationCommandInteractionDataOptionValue,
+    },
+    model::voice::VoiceState,
+    prelude::{Mentionable, TypeMapKey},
+};
+use std::{collections::HashMap, sync::Arc};
+use tokio::sync::RwLock;
+
+/// User the bot moves along with, per guild.
+pub struct Following;
+
+impl TypeMapKey for Following {
+    type Value = Arc<RwLock<HashMap<GuildId, UserId>>>;
+}
+
+pub async fn follow(ctx: &Context, cmd: &ApplicationCommandInteraction) -> Res {
+    check_msg(
+        cmd.create_interaction_response(&ctx.http, |response| {
+            defer_interaction(response, None, false)
+        })
+        .await,
+    );
+
+    let guild_id = cmd.guild_id.unwrap();
+    let lang = i18n::lang(ctx, cmd.guild_id, &cmd.locale).await;
+
+    let user = match cmd.data.options.first().and_then(|o| o.resolved.as_ref()) {
+        Some(ApplicationCommandInteractionDataOptionValue::User(user, _)) if !user.bot => user,
+        _ => {
+            check_msg(
+                cmd.edit_original_interaction_response(&ctx.http, |response| {
+                    response.content(tr(lang, "follow.no_user", &[]))
+                })
+                .await,
+            );
+            return Ok(());
+        }
+    };
+
+    let channel_id = match voice_channel_of(ctx, guild_id, user.id).await {
+        Some(channel_id) => channel_id,
+        None => {
+            check_msg(
+                cmd.edit_original_interaction_response(&ctx.http, |response| {
+                    response.content(tr(lang, "follow.not_in_voice", &[&user.mention()]))
+                })
+                .await,
+            );
+            return Ok(());
+        }
+    };
+
+    let chan_id = announce::invoked(ctx, guild_id)
+        .await
+        .unwrap_or(cmd.channel_id);
+
+    let content = match join_channel(ctx, guild_id, channel_id, chan_id).await {
+        Ok(_) => {
+            set(ctx, guild_id, Some(user.id)).await;
+            tr(
+                lang,
+                "follow.following",
+                &[&cmd.user.mention(), &user.mention()],
+            )
+        }
+        Err(why) => {
+            println!("Err joining to follow: {:?}", why);
+            tr(lang, "join_error", &[])
+        }
+    };
+
+    check_msg(
+        cmd.edit_original_interaction_response(&ctx.http, |response| response.content(content))
+            .await,
+    );
+
+    Ok(())
+}
+
+pub async fn unfollow(ctx: &Context, cmd: &ApplicationCommandInteraction) -> Res {
+    check_msg(
+        cmd.create_interaction_response(&ctx.http, |response| {
+            defer_interaction(response, None, false)
+        })
+        .await,
+    );
+
+    let lang = i18n::lang(ctx, cmd.guild_id, &cmd.locale).await;
+    let content = match set(ctx, cmd.guild_id.unwrap(), None).await {
+        Some(user_id) => tr(
+            lang,
+            "follow.stopped",
+            &[&cmd.user.mention(), &user_id.mention()],
+        ),
+        None => tr(lang, "follow.nobody", &[]),
+    };
+
+    check_msg(
+        cmd.edit_original_interaction_response(&ctx.http, |response| response.content(content))
+            .await,
+    );
+
+    Ok(())
+}
+
+/// Changes who is followed in a guild, returns who was before.
+pub async fn set(ctx: &Context, guild_id: GuildId, user_id: Option<UserId>) -> Option<UserId> {
+    let data = ctx.data.read().await;
+    let mut following = data
+        .get::<Following>()
+        .expect("Following placed in at initialisation.")
+        .write()
+        .await;
+
+    match user_id {
+        Some(user_id) => following.insert(guild_id, user_id),
+        None => following.remove(&guild_id),
+    }
+}
+
+async fn followed(ctx: &Context, guild_id: GuildId) -> Option<UserId> {
+    let data = ctx.data.read().await;
+    let following = data
+        .get::<Following>()
+        .expect("Following placed in at initialisation.")
+        .read()
+        .await;
+
+    following.get(&guild_id).copied()
+}
+
+/// Moves along with the followed user, called on every voice state update.
+pub async fn check(ctx: &Context, guild_id: GuildId, state: &VoiceState) {
+    if followed(ctx, guild_id).await != Some(state.user_id) {
+        return;
+    }
+
+    let channel_id = match state.channel_id {
+        Some(channel_id) => channel_id,
+        None => {
+            set(ctx, guild_id, None).await;
+            let lang = i18n::guild_lang(ctx, guild_id).await;
+            announce::say(
+                ctx,
+                guild_id,
+                tr(lang, "follow.left_voice", &[&state.user_id.mention()]),
+            )
+            .await;
+            return;
+        }
+    };
+
+    let manager = songbird::get(ctx)
+        .await
+        .expect("Songbird Voice client placed in at initialisation.")
+        .clone();
+
+    let current = match manager.get(guild_id) {
+        Some(handler_lock) => handler_lock.lock().await.current_channel(),
+        None => None,
+    };
+
+    if current.map(|c| ChannelId(c.0)) == Some(channel_id) {
+        return;
+    }
+
+    let chan_id = match announce::invoked(ctx, guild_id).await {
+        Some(chan_id) => chan_id,
+        None => return,
+    };
+
+    if let Err(why) = join_channel(ctx, guild_id, channel_id, chan_id).await {
+        println!("Err following into {}: {:?}", channel_id, why);
+    }
+}
@@ -0,0 +1,230 @@
+use crate::cmd::{check_msg, defer_interaction, Res};
+use crate::i18n::{self, tr};
+use crate::{mix, settings};
+use serenity::{
+    client::Context,
+    model::id::{ChannelId, GuildId, UserId},
+    model::interactions::application_command::{
+        ApplicationCommandInteraction, ApplicationCommandInteractionDataOptionValue,
+    },
+    model::voice::VoiceState,
+};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+use tokio::process::Command;
+use url::Url;
+
+/// Hosts Discord serves uploaded files from.
+const ATTACHMENT_HOSTS: [&str; 2] = ["cdn.discordapp.com", "media.discordapp.net"];
+
+pub async fn jingle(ctx: &Context, cmd: &ApplicationCommandInteraction) -> Res {
+    check_msg(
+        cmd.create_interaction_response(&ctx.http, |response| {
+            defer_interaction(response, None, true)
+        })
+        .await,
+    );
+
+    let guild_id = cmd.guild_id.unwrap();
+    let lang = i18n::lang(ctx, cmd.guild_id, &cmd.locale).await;
+    let sub = cmd.data.options.first().expect("Expected subcommand");
+    let path = path(ctx, guild_id, cmd.user.id).await;
+
+    let content = match sub.name.as_str() {
+        "set" => {
+            let url = match sub.options.first().and_then(|o| o.resolved.as_ref()) {
+                Some(ApplicationCommandInteractionDataOptionValue::String(url))
+                    if is_attachment(url) =>
+                {
+                    url.clone()
+                }
+                _ => {
+                    check_msg(
+                        cmd.edit_original_interaction_response(&ctx.http, |response| {
+                            response.content(tr(lang, "jingle.not_attachment", &[]))
+                        })
+                        .await,
+                    );
+                    return Ok(());
+                }
+            };
+
+            let seconds = settings::get(ctx, guild_id).await.jingle_length;
+            match store(&url, &path, seconds).await {
+                true => tr(lang, "jingle.set", &[&seconds]),
+                false => tr(lang, "jingle.failed", &[]),
+            }
+        }
+        _ => match fs::remove_file(&path) {
+            Ok(_) => tr(lang, "jingle.cleared", &[]),
+            Err(_) => tr(lang, "jingle.none", &[]),
+        },
+    };
+
+    check_msg(
+        cmd.edit_original_interaction_response(&ctx.http, |response| response.content(content))
+            .await,
+    );
+
+    Ok(())
+}
+
+/// Plays the jingle of whoever just joined the bot's voice channel, called on
+/// every voice state update.
+pub async fn greet(ctx: &Context, guild_id: GuildId, old: Option<&VoiceState>, new: &VoiceState) {
+    let channel_id = match new.channel_id {
+        Some(channel_id) if old.and_then(|o| o.channel_id) != Some(channel_id) => channel_id,
+        _ => return,
+    };
+
+    if new.member.as_ref().is_some_and(|m| m.user.bot) {
+        return;
+    }
+
+    let manager = songbird::get(ctx)
+        .await
+        .expect("Songbird Voice client placed in at initialisation.")
+        .clone();
+
+    let handler_lock = match manager.get(guild_id) {
+        Some(handler_lock) => handler_lock,
+        None => return,
+    };
+
+    let current = handler_lock.lock().await.current_channel();
+    if current.map(|c| ChannelId(c.0)) != Some(channel_id) {
+        return;
+    }
+
+    let settings = settings::get(ctx, guild_id).await;
+    if !settings.jingles {
+        return;
+    }
+
+    let path = path(ctx, guild_id, new.user_id).await;
+    if !path.exists() {
+        return;
+    }
+
+    let source = match songbird::ffmpeg(&path).await {
+        Ok(source) => source,
+        Err(why) => {
+            println!("Err starting jingle {:?}: {:?}", path, why);
+            return;
+        }
+    };
+
+    let volume = settings.jingle_volume as f32 / 100.0;
+    let track = mix::play_over(ctx, guild_id, &handler_lock, source, volume).await;
+
+    // The length cap may have been lowered since the jingle was stored.
+    let cap = Duration::from_secs(settings.jingle_length);
+    tokio::spawn(async move {
+        tokio::time::sleep(cap).await;
+        let _ = track.stop();
+    });
+}
+
+async fn path(ctx: &Context, guild_id: GuildId, user_id: UserId) -> PathBuf {
+    settings::data_dir(ctx)
+        .await
+        .join("jingles")
+        .join(guild_id.to_string())
+        .join(format!("{}.ogg", user_id))
+}
+
+/// Whether `url` links to a file uploaded to Discord, the only place jingles
+/// are fetched from.
+fn is_attachment(url: &str) -> bool {
+    let url = match Url::parse(url) {
+        Ok(url) => url,
+        Err(_) => return false,
+    };
+
+    url.scheme() == "https"
+        && url.username().is_empty()
+        && url.password().is_none()
+        && url.port().is_none()
+        && url
+            .host_str()
+            .is_some_and(|host| ATTACHMENT_HOSTS.contains(&host))
+        && url.path().starts_with("/attachments/")
+}
+
+/// Fetches the first `seconds` of the audio at `url` into `path`.
+async fn store(url: &str, path: &Path, seconds: u64) -> bool {
+    if let Some(dir) = path.parent() {
+        let _ = fs::create_dir_all(dir);
+    }
+
+    // Keep any previous jingle until the new one is complete.
+    let part = path.with_extension("part.ogg");
+
+    let status = Command::new("ffmpeg")
+        .args(["-nostdin", "-loglevel", "error", "-y"])
+        // Nor should whatever the file links to be fetched from elsewhere.
+        .args(["-protocol_whitelist", "https,tls,tcp", "-i"])
+        .arg(url)
+        .arg("-t")
+        .arg(seconds.to_string())
+        .args(["-vn", "-c:a", "libopus", "-b:a", "96k"])
+        .arg(&part)
+        .status()
+        .await;
+
+    match status {
+        Ok(status) if status.success() => {}
+        Ok(status) => {
+            println!("Err storing jingle from {}: {}", url, status);
+            let _ = fs::remove_file(&part);
+            return false;
+        }
+        Err(why) => {
+            println!("Err running ffmpeg: {:?}", why);
+            return false;
+        }
+    }
+
+    match fs::rename(&part, path) {
+        Ok(_) => true,
+        Err(why) => {
+            println!("Err storing jingle {:?}: {:?}", path, why);
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_discord_attachments_are_fetched() {
+        assert!(is_attachment(
+            "https://cdn.discordapp.com/attachments/1/2/intro.mp3"
+        ));
+        assert!(is_attachment(
+            "https://media.discordapp.net/attachments/1/2/intro.ogg?ex=1"
+        ));
+
+        assert!(!is_attachment(
+            "http://cdn.discordapp.com/attachments/1/2/a.mp3"
+        ));
+        assert!(!is_attachment("https://cdn.discordapp.com/avatars/1/2.png"));
+        assert!(!is_attachment(
+            "https://cdn.discordapp.com:8080/attachments/1/2/a.mp3"
+        ));
+        assert!(!is_attachment(
+            "https://cdn.discordapp.com@169.254.169.254/attachments/a"
+        ));
+        assert!(!is_attachment(
+            "https://cdn.discordapp.com.evil.test/attachments/1/2/a.mp3"
+        ));
+        assert!(!is_attachment("https://localhost/attachments/1/2/a.mp3"));
+        assert!(!is_attachment("file:///etc/passwd"));
+        assert!(!is_attachment("not a url"));
+    }
+}
@@ -1,58 +1,180 @@
-use crate::cmd::{check_msg, defer_interaction, now_playing_embed, Res};
+use crate::cmd::autoplay::related_track;
+use crate::cmd::{
+    check_msg, defer_interaction, enqueue, track_info, voice_channel_of, Res, TrackInfo,
+};
+use crate::cmd::{leave, remove, sleep};
+use crate::i18n::{self, tr, Lang};
+use crate::{announce, history, mix, progress, settings, stage, tts};
 use serenity::async_trait;
 use serenity::client::Context;
-use serenity::http::Http;
 use serenity::model::channel::ChannelType;
 use serenity::model::gateway::Activity;
-use serenity::model::id::ChannelId;
+use serenity::model::id::{ChannelId, GuildId};
 use serenity::model::interactions::application_command::{
     ApplicationCommandInteraction, ApplicationCommandInteractionDataOptionValue,
 };
 use serenity::prelude::Mentionable;
-use songbird::{Call, Event, EventContext, EventHandler as VoiceEventHandler, TrackEvent};
+use songbird::error::JoinResult;
+use songbird::events::context_data::{DisconnectKind, DisconnectReason};
+use songbird::input::Restartable;
+use songbird::model::CloseCode;
+use songbird::tracks::{PlayMode, TrackHandle};
+use songbird::{
+    Call, CoreEvent, Event, EventContext, EventHandler as VoiceEventHandler, TrackEvent,
+};
 use std::{
-    sync::atomic::{AtomicUsize, Ordering},
+    sync::atomic::{AtomicU64, Ordering},
     sync::Arc,
+    time::Duration,
 };
-use tokio::sync::Mutex;
+use tokio::{sync::Mutex, task::JoinHandle};
 
 struct TrackEndNotifier {
-    chan_id: ChannelId,
-    http: Arc<Http>,
+    guild_id: GuildId,
+    /// Shared with the `IdleNotifier`, tracks playing in between its checks
+    /// count too.
+    idle_minutes: Arc<AtomicU64>,
     handler_lock: Arc<Mutex<Call>>,
     ctx: Arc<Mutex<Context>>,
 }
 
+impl TrackEndNotifier {
+    /// Queues a track related to the last played one, if the guild wants it.
+    async fn autoplay(&self, app_ctx: &Context, last: Option<String>) -> Option<TrackHandle> {
+        if !settings::get(app_ctx, self.guild_id).await.autoplay {
+            return None;
+        }
+
+        let history = history::recent(app_ctx, self.guild_id).await;
+        let last = last.or_else(|| history.first().map(|p| p.url.clone()))?;
+        let url = related_track(&last, &history).await?;
+
+        let source = match Restartable::ytdl(url, true).await {
+            Ok(source) => source,
+            Err(why) => {
+                println!("Err starting autoplay source: {:?}", why);
+                return None;
+            }
+        };
+
+        let mut handler = self.handler_lock.lock().await;
+        // Someone may have queued something while we were looking.
+        if !handler.queue().is_empty() {
+            return None;
+        }
+
+        let info = TrackInfo {
+            autoplay: true,
+            ..Default::default()
+        };
+
+        Some(enqueue(&mut handler, source.into(), info).await)
+    }
+
+    /// Lets everyone know `np` started playing, `channel` being the voice one.
+    async fn playing(
+        &self,
+        app_ctx: &Context,
+        np: TrackHandle,
+        channel: Option<ChannelId>,
+        lang: Lang,
+    ) {
+        let metadata = np.metadata();
+        let title = metadata.title.clone().unwrap();
+        let info = track_info(&np).await;
+
+        app_ctx.set_activity(Activity::listening(&title)).await;
+        if let Some(channel) = channel {
+            stage::topic(app_ctx, channel, &title).await;
+        }
+        self.speak_next(app_ctx, &title, info, lang).await;
+
+        if let Some(message) =
+            announce::show_now_playing(app_ctx, self.guild_id, metadata, info, lang).await
+        {
+            progress::follow(app_ctx, self.guild_id, &message, np.clone(), info).await;
+        }
+    }
+
+    /// Tells listeners what is playing next, if the guild wants it.
+    async fn speak_next(&self, app_ctx: &Context, title: &str, info: TrackInfo, lang: Lang) {
+        if !settings::get(app_ctx, self.guild_id)
+            .await
+            .spoken_announcements
+        {
+            return;
+        }
+
+        let requester = match info.requester {
+            Some(user_id) => match app_ctx.cache.member(self.guild_id, user_id).await {
+                Some(member) => Some(member.display_name().into_owned()),
+                None => user_id.to_user(app_ctx).await.ok().map(|u| u.name),
+            },
+            None => None,
+        };
+
+        let text = match requester {
+            Some(name) => tr(lang, "np.next_up_by", &[&title, &name]),
+            None => tr(lang, "np.next_up", &[&title]),
+        };
+
+        if let Some((source, path)) = tts::speech(self.guild_id, &text, lang).await {
+            let track =
+                mix::play_over(app_ctx, self.guild_id, &self.handler_lock, source, 1.0).await;
+            tts::remove_after(&track, path);
+        }
+    }
+}
+
 #[async_trait]
 impl VoiceEventHandler for TrackEndNotifier {
     async fn act(&self, ctx: &EventContext<'_>) -> Option<Event> {
-        if let EventContext::Track(_track_list) = ctx {
-            let handler = self.handler_lock.lock().await;
-            if let Some(np) = handler.queue().current() {
-                let metadata = np.metadata();
+        if let EventContext::Track(track_list) = ctx {
+            // Sounds played over the music and tracks taken out of the queue
+            // are none of our business.
+            let mut ended = Vec::new();
+            for (_, track) in track_list.iter() {
+                if !mix::is_overlay(track).await && !remove::is_removed(track).await {
+                    ended.push(*track);
+                }
+            }
+            if ended.is_empty() {
+                return None;
+            }
+            self.idle_minutes.store(0, Ordering::Relaxed);
 
-                let app_ctx = self.ctx.lock().await;
-                app_ctx
-                    .set_activity(Activity::listening(metadata.title.clone().unwrap()))
-                    .await;
+            let app_ctx = self.ctx.lock().await.clone();
+            let lang = i18n::guild_lang(&app_ctx, self.guild_id).await;
 
-                check_msg(
-                    self.chan_id
-                        .send_message(&self.http, |m| {
-                            now_playing_embed(m, metadata.clone());
-                            m
-                        })
-                        .await,
-                );
+            progress::forget(&app_ctx, self.guild_id).await;
+            for track in ended.iter() {
+                history::record(&app_ctx, self.guild_id, track.metadata()).await;
+            }
+
+            let (current, channel) = {
+                let handler = self.handler_lock.lock().await;
+                (handler.queue().current(), handler.current_channel())
+            };
+            let channel = channel.map(|c| ChannelId(c.0));
+            if sleep::on_track_end(&app_ctx, self.guild_id, current.is_none()).await {
+                return None;
+            }
+
+            if let Some(np) = current {
+                self.playing(&app_ctx, np, channel, lang).await;
             } else {
-                let app_ctx = self.ctx.lock().await;
+                let last = ended
+                    .last()
+                    .and_then(|track| track.metadata().source_url.clone());
+
+                if let Some(np) = self.autoplay(&app_ctx, last).await {
+                    self.playing(&app_ctx, np, channel, lang).await;
+                    return None;
+                }
+
                 app_ctx.reset_presence().await;
 
-                check_msg(
-                    self.chan_id
-                        .say(&self.http, "🕳 Queue is empty! That's sad... I guess...")
-                        .await,
-                );
+                announce::say(&app_ctx, self.guild_id, tr(lang, "np.queue_empty", &[])).await;
             }
         }
 
@@ -60,27 +182,163 @@ impl VoiceEventHandler for TrackEndNotifier {
     }
 }
 
-struct ChannelDurationNotifier {
-    chan_id: ChannelId,
-    count: Arc<AtomicUsize>,
-    http: Arc<Http>,
+struct IdleNotifier {
+    guild_id: GuildId,
+    idle_minutes: Arc<AtomicU64>,
+    handler_lock: Arc<Mutex<Call>>,
+    ctx: Arc<Mutex<Context>>,
 }
 
 #[async_trait]
-impl VoiceEventHandler for ChannelDurationNotifier {
+impl VoiceEventHandler for IdleNotifier {
     async fn act(&self, _ctx: &EventContext<'_>) -> Option<Event> {
-        let count_before = self.count.fetch_add(1, Ordering::Relaxed);
-        check_msg(
-            self.chan_id
-                .say(
-                    &self.http,
-                    &format!(
-                        "I've been in this channel for {} minutes!",
-                        count_before + 1
-                    ),
-                )
-                .await,
-        );
+        if !self.handler_lock.lock().await.queue().is_empty() {
+            self.idle_minutes.store(0, Ordering::Relaxed);
+            return None;
+        }
+
+        let idle_minutes = self.idle_minutes.fetch_add(1, Ordering::Relaxed) + 1;
+        let app_ctx = self.ctx.lock().await.clone();
+        let timeout = settings::get(&app_ctx, self.guild_id).await.idle_timeout;
+
+        if timeout == 0 || idle_minutes < timeout {
+            return None;
+        }
+
+        // Leaving drops the driver running this very handler.
+        let guild_id = self.guild_id;
+        tokio::spawn(async move {
+            let lang = i18n::guild_lang(&app_ctx, guild_id).await;
+            let notice = tr(lang, "idle.left", &[&idle_minutes]);
+            leave::disconnect_saying(&app_ctx, guild_id, notice).await;
+        });
+
+        Some(Event::Cancel)
+    }
+}
+
+/// Attempts at rejoining, waiting twice as long before each new one.
+const RECONNECT_ATTEMPTS: usize = 5;
+
+/// What a dropped connection left to undo, shared by a call's notifiers.
+#[derive(Default)]
+struct Dropped {
+    /// Whether playback was paused because of it.
+    paused: bool,
+    /// Rejoin attempts going on, only one series at a time.
+    rejoin: Option<JoinHandle<()>>,
+}
+
+/// Gets the bot back in its voice channel after the connection dropped.
+struct ReconnectNotifier {
+    guild_id: GuildId,
+    dropped: Arc<Mutex<Dropped>>,
+    handler_lock: Arc<Mutex<Call>>,
+    ctx: Arc<Mutex<Context>>,
+}
+
+impl ReconnectNotifier {
+    async fn resume(&self) {
+        let paused = std::mem::take(&mut self.dropped.lock().await.paused);
+        if paused {
+            let _ = self.handler_lock.lock().await.queue().resume();
+        }
+    }
+}
+
+#[async_trait]
+impl VoiceEventHandler for ReconnectNotifier {
+    async fn act(&self, ctx: &EventContext<'_>) -> Option<Event> {
+        match ctx {
+            EventContext::DriverDisconnect(data) => {
+                // No reason means we left or moved on purpose, and being
+                // disconnected by someone is not worth fighting. Failing to
+                // connect is for whoever asked to join to deal with, be it
+                // `/join`, `/follow` or our own attempts below.
+                match (data.kind, data.reason) {
+                    (DisconnectKind::Connect, _)
+                    | (_, None)
+                    | (_, Some(DisconnectReason::WsClosed(Some(CloseCode::Disconnected)))) => {
+                        return None
+                    }
+                    _ => {}
+                }
+                let channel_id = data.channel_id?;
+
+                let mut dropped = self.dropped.lock().await;
+                if dropped.rejoin.is_some() {
+                    return None;
+                }
+
+                let current = self.handler_lock.lock().await.queue().current();
+                let state = match &current {
+                    Some(track) => track.get_info().await.ok(),
+                    None => None,
+                };
+                let position = state.as_ref().map(|state| state.position);
+                // Whatever someone paused stays paused.
+                if let (Some(track), Some(state)) = (&current, &state) {
+                    if state.playing == PlayMode::Play {
+                        let _ = track.pause();
+                        dropped.paused = true;
+                    }
+                }
+
+                let app_ctx = self.ctx.lock().await.clone();
+                let guild_id = self.guild_id;
+                let notifier = Self {
+                    guild_id,
+                    dropped: self.dropped.clone(),
+                    handler_lock: self.handler_lock.clone(),
+                    ctx: self.ctx.clone(),
+                };
+
+                dropped.rejoin = Some(tokio::spawn(async move {
+                    let manager = songbird::get(&app_ctx)
+                        .await
+                        .expect("Songbird Voice client placed in at initialisation.")
+                        .clone();
+
+                    let mut rejoined = false;
+                    let mut delay = Duration::from_secs(1);
+                    for _ in 0..RECONNECT_ATTEMPTS {
+                        tokio::time::sleep(delay).await;
+                        delay *= 2;
+
+                        // Someone may have told us to leave in the meantime.
+                        if manager.get(guild_id).is_none() {
+                            break;
+                        }
+
+                        let (_, success) = manager.join(guild_id, channel_id).await;
+                        if success.is_ok() {
+                            rejoined = true;
+                            break;
+                        }
+                    }
+                    notifier.dropped.lock().await.rejoin = None;
+
+                    if rejoined {
+                        stage::speak(&app_ctx, ChannelId(channel_id.0)).await;
+                        if let (Some(track), Some(position)) = (&current, position) {
+                            let _ = track.seek_time(position);
+                        }
+                        notifier.resume().await;
+                    } else if manager.get(guild_id).is_some() {
+                        let lang = i18n::guild_lang(&app_ctx, guild_id).await;
+                        announce::say(&app_ctx, guild_id, tr(lang, "reconnect.failed", &[])).await;
+                    }
+                }));
+            }
+            // Songbird got the connection back on its own.
+            EventContext::DriverReconnect(_) => {
+                if let Some(rejoin) = self.dropped.lock().await.rejoin.take() {
+                    rejoin.abort();
+                }
+                self.resume().await;
+            }
+            _ => {}
+        }
 
         None
     }
@@ -95,86 +353,140 @@ pub async fn join(ctx: &Context, cmd: &ApplicationCommandInteraction) -> Res {
     );
 
     let guild = ctx.cache.guild(cmd.guild_id.unwrap()).await.unwrap();
+    let lang = i18n::lang(ctx, cmd.guild_id, &cmd.locale).await;
 
-    let channel_option = cmd
-        .data
-        .options
-        .get(0)
-        .expect("Expected user option")
-        .resolved
-        .as_ref()
-        .unwrap();
+    let channel_option = cmd.data.options.first().and_then(|o| o.resolved.as_ref());
 
     let channel = match channel_option {
-        ApplicationCommandInteractionDataOptionValue::Channel(channel) => match channel.kind {
-            ChannelType::Voice => ctx.cache.channel(channel.id).await.unwrap(),
-            _ => {
-                check_msg(
-                    cmd.edit_original_interaction_response(&ctx.http, |response| {
-                        response.content("Must be a voice channel")
-                    })
-                    .await,
-                );
-                return Ok(());
+        Some(ApplicationCommandInteractionDataOptionValue::Channel(channel)) => {
+            match channel.kind {
+                ChannelType::Voice | ChannelType::Stage => {
+                    ctx.cache.channel(channel.id).await.unwrap()
+                }
+                _ => {
+                    check_msg(
+                        cmd.edit_original_interaction_response(&ctx.http, |response| {
+                            response.content(tr(lang, "not_voice_channel", &[]))
+                        })
+                        .await,
+                    );
+                    return Ok(());
+                }
             }
-        },
-        _ => {
+        }
+        Some(_) => {
             check_msg(
                 cmd.edit_original_interaction_response(&ctx.http, |response| {
-                    response.content("Must provide a channel")
+                    response.content(tr(lang, "join.no_channel", &[]))
                 })
                 .await,
             );
             return Ok(());
         }
+        // Without a channel, join the caller where they are.
+        None => match voice_channel_of(ctx, guild.id, cmd.user.id).await {
+            Some(channel_id) => ctx.cache.channel(channel_id).await.unwrap(),
+            None => {
+                check_msg(
+                    cmd.edit_original_interaction_response(&ctx.http, |response| {
+                        response.content(tr(lang, "join.caller_not_in_voice", &[]))
+                    })
+                    .await,
+                );
+                return Ok(());
+            }
+        },
     };
 
+    if join_channel(ctx, guild.id, channel.id(), cmd.channel_id)
+        .await
+        .is_ok()
+    {
+        check_msg(
+            cmd.edit_original_interaction_response(&ctx.http, |response| {
+                response.content(tr(lang, "join.joined", &[&channel.mention()]))
+            })
+            .await,
+        );
+    } else {
+        check_msg(
+            cmd.edit_original_interaction_response(&ctx.http, |response| {
+                response.content(tr(lang, "join_error", &[]))
+            })
+            .await,
+        );
+    }
+
+    Ok(())
+}
+
+/// Joins `channel_id` and hooks track notifications up, `chan_id` being the
+/// text channel announcements go to unless the guild set another.
+pub async fn join_channel(
+    ctx: &Context,
+    guild_id: GuildId,
+    channel_id: ChannelId,
+    chan_id: ChannelId,
+) -> JoinResult<Arc<Mutex<Call>>> {
     let manager = songbird::get(ctx)
         .await
         .expect("Songbird Voice client placed in at initialisation.")
         .clone();
 
-    let (handle_lock, success) = manager.join(guild.id, channel.id()).await;
+    // Events are only registered once per call, not on every channel move.
+    let fresh = manager.get(guild_id).is_none();
+
+    let (handle_lock, success) = manager.join(guild_id, channel_id).await;
+
+    if let Err(e) = success {
+        if fresh {
+            let _ = manager.remove(guild_id).await;
+        }
+        return Err(e);
+    }
+
+    announce::set(ctx, guild_id, chan_id).await;
+    stage::speak(ctx, channel_id).await;
+
+    if fresh {
+        announce::forget_now_playing(ctx, guild_id).await;
 
-    if let Ok(_channel) = success {
-        let chan_id = cmd.channel_id;
-        let send_http = ctx.http.clone();
         let mut handle = handle_lock.lock().await;
+        let idle_minutes = Arc::new(AtomicU64::new(0));
 
         handle.add_global_event(
             Event::Track(TrackEvent::End),
             TrackEndNotifier {
-                chan_id,
-                http: send_http,
+                guild_id,
+                idle_minutes: idle_minutes.clone(),
                 handler_lock: handle_lock.clone(),
                 ctx: Arc::new(Mutex::new(ctx.clone())),
             },
         );
 
-        // let send_http = ctx.http.clone();
-        // handle.add_global_event(
-        //     Event::Periodic(Duration::from_secs(60), None),
-        //     ChannelDurationNotifier {
-        //         chan_id,
-        //         count: Default::default(),
-        //         http: send_http,
-        //     },
-        // );
+        let dropped = Arc::new(Mutex::new(Dropped::default()));
+        for event in [CoreEvent::DriverDisconnect, CoreEvent::DriverReconnect] {
+            handle.add_global_event(
+                Event::Core(event),
+                ReconnectNotifier {
+                    guild_id,
+                    dropped: dropped.clone(),
+                    handler_lock: handle_lock.clone(),
+                    ctx: Arc::new(Mutex::new(ctx.clone())),
+                },
+            );
+        }
 
-        check_msg(
-            cmd.edit_original_interaction_response(&ctx.http, |response| {
-                response.content(format!("Joined {}", channel.mention()))
-            })
-            .await,
-        );
-    } else {
-        check_msg(
-            cmd.edit_original_interaction_response(&ctx.http, |response| {
-                response.content("Error joining the channel")
-            })
-            .await,
+        handle.add_global_event(
+            Event::Periodic(Duration::from_secs(60), None),
+            IdleNotifier {
+                guild_id,
+                idle_minutes,
+                handler_lock: handle_lock.clone(),
+                ctx: Arc::new(Mutex::new(ctx.clone())),
+            },
         );
     }
 
-    Ok(())
+    Ok(handle_lock)
 }
@@ -0,0 +1,88 @@
+use crate::cmd::{check_msg, defer_interaction, Invocation, Res};
+use crate::dj::Access;
+use crate::i18n::{self, tr};
+use crate::vote::{self, Motion};
+use serenity::{
+    client::Context,
+    model::interactions::application_command::{
+        ApplicationCommandInteraction, ApplicationCommandInteractionDataOptionValue,
+    },
+};
+use songbird::tracks::{TrackHandle, TrackQueue};
+
+pub async fn jump(ctx: &Context, cmd: &ApplicationCommandInteraction, access: Access) -> Res {
+    check_msg(
+        cmd.create_interaction_response(&ctx.http, |response| {
+            defer_interaction(response, None, false)
+        })
+        .await,
+    );
+
+    let position = match cmd.data.options.first().and_then(|o| o.resolved.as_ref()) {
+        Some(ApplicationCommandInteractionDataOptionValue::Integer(position)) => *position,
+        _ => 0,
+    };
+
+    let manager = songbird::get(ctx)
+        .await
+        .expect("Songbird Voice client placed in at initialisation.")
+        .clone();
+
+    let lang = i18n::lang(ctx, cmd.guild_id, &cmd.locale).await;
+    let track = match manager.get(cmd.guild_id.unwrap()) {
+        Some(handler_lock) => {
+            let queue = handler_lock.lock().await.queue().current_queue();
+            match position {
+                p if p >= 2 => queue.get(p as usize - 1).cloned(),
+                _ => None,
+            }
+        }
+        None => {
+            check_msg(
+                cmd.edit_original_interaction_response(&ctx.http, |response| {
+                    response.content(tr(lang, "not_playing", &[]))
+                })
+                .await,
+            );
+            return Ok(());
+        }
+    };
+
+    let track = match track {
+        Some(track) => track,
+        None => {
+            check_msg(
+                cmd.edit_original_interaction_response(&ctx.http, |response| {
+                    response.content(tr(lang, "no_track_at", &[&position]))
+                })
+                .await,
+            );
+            return Ok(());
+        }
+    };
+
+    let cmd = Invocation::Command(cmd);
+    match access {
+        Access::Dj => vote::bypass(ctx, &cmd, Motion::Jump(track), "vote.by_dj").await,
+        Access::Open | Access::Vote => vote::hold(ctx, &cmd, Motion::Jump(track)).await,
+    }
+}
+
+/// Brings `track` right after the current one, then skips to it. Returns
+/// whether it was still waiting in the queue.
+pub fn jump_to(queue: &TrackQueue, track: &TrackHandle) -> bool {
+    let moved = queue.modify_queue(|queue| {
+        let index = queue
+            .iter()
+            .skip(1)
+            .position(|t| t.uuid() == track.uuid())?;
+        let track = queue.remove(index + 1)?;
+        queue.insert(1, track);
+        Some(())
+    });
+
+    if moved.is_some() {
+        let _ = queue.skip();
+    }
+    moved.is_some()
+}
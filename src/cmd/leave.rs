@@ -1,47 +1,77 @@
-use crate::cmd::{check_msg, defer_interaction, Res};
+use crate::cmd::remove::stop_queue;
+use crate::cmd::{check_msg, defer_interaction, follow, sleep, Invocation, Res};
+use crate::dj::Access;
+use crate::i18n::{self, tr};
+use crate::vote::{self, Motion};
+use crate::{announce, progress};
 use serenity::{
-    client::Context, model::interactions::application_command::ApplicationCommandInteraction,
+    client::Context, model::id::GuildId,
+    model::interactions::application_command::ApplicationCommandInteraction,
 };
 
-pub async fn leave(ctx: &Context, cmd: &ApplicationCommandInteraction) -> Res {
+pub async fn leave(ctx: &Context, cmd: &ApplicationCommandInteraction, access: Access) -> Res {
+    // Votes are for everyone to see.
     check_msg(
         cmd.create_interaction_response(&ctx.http, |response| {
-            defer_interaction(response, None, true)
+            defer_interaction(response, None, access != Access::Vote)
         })
         .await,
     );
 
-    let guild_id = cmd.guild_id.unwrap();
+    if access == Access::Vote {
+        return vote::hold(ctx, &Invocation::Command(cmd), Motion::Leave).await;
+    }
+
+    let lang = i18n::lang(ctx, cmd.guild_id, &cmd.locale).await;
+    let content = match disconnect(ctx, cmd.guild_id.unwrap()).await {
+        true => tr(lang, "leave.left", &[]),
+        false => tr(lang, "not_in_voice", &[]),
+    };
+
+    check_msg(
+        cmd.edit_original_interaction_response(&ctx.http, |response| response.content(content))
+            .await,
+    );
 
+    Ok(())
+}
+
+/// Stops playback and leaves the guild's voice channel, returns whether it
+/// was in one.
+pub async fn disconnect(ctx: &Context, guild_id: GuildId) -> bool {
     let manager = songbird::get(ctx)
         .await
         .expect("Songbird Voice client placed in at initialisation.")
         .clone();
-    let has_handler = manager.get(guild_id).is_some();
-
-    if has_handler {
-        if let Err(e) = manager.remove(guild_id).await {
-            check_msg(
-                cmd.channel_id
-                    .say(&ctx.http, &format!("Failed: {:?}", e))
-                    .await,
-            );
-        }
 
-        check_msg(
-            cmd.edit_original_interaction_response(&ctx.http, |response| {
-                response.content("Left voice channel")
-            })
-            .await,
-        );
-    } else {
-        check_msg(
-            cmd.edit_original_interaction_response(&ctx.http, |response| {
-                response.content("Not in a voice channel")
-            })
-            .await,
-        );
+    let handler_lock = match manager.get(guild_id) {
+        Some(handler_lock) => handler_lock,
+        None => return false,
+    };
+
+    stop_queue(handler_lock.lock().await.queue()).await;
+    progress::forget(ctx, guild_id).await;
+    sleep::cancel(ctx, guild_id).await;
+    follow::set(ctx, guild_id, None).await;
+
+    if let Err(e) = manager.remove(guild_id).await {
+        println!("Err leaving voice channel: {:?}", e);
     }
 
-    Ok(())
+    ctx.reset_presence().await;
+    true
+}
+
+/// Leaves like `disconnect` on the bot's own initiative, posting `notice` where
+/// the guild's announcements went if it was in a voice channel.
+pub async fn disconnect_saying(ctx: &Context, guild_id: GuildId, notice: String) {
+    // Found before leaving, the voice channel's chat is only known through
+    // the call.
+    let chan_id = announce::channel(ctx, guild_id).await;
+
+    if disconnect(ctx, guild_id).await {
+        if let Some(chan_id) = chan_id {
+            check_msg(chan_id.say(&ctx.http, notice).await);
+        }
+    }
 }
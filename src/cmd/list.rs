@@ -1,9 +1,16 @@
 use serenity::{
-    builder::CreateEmbed, client::Context,
-    model::interactions::application_command::ApplicationCommandInteraction,
+    builder::CreateEmbed,
+    client::Context,
+    model::interactions::{
+        application_command::ApplicationCommandInteraction, autocomplete::AutocompleteInteraction,
+    },
 };
 
-use crate::cmd::{check_msg, defer_interaction, duration_format, Res};
+use crate::cmd::{
+    autocomplete_input, check_msg, choice_name, defer_interaction, duration_format, Res,
+    MAX_CHOICES,
+};
+use crate::i18n::{self, tr};
 
 pub async fn list(ctx: &Context, cmd: &ApplicationCommandInteraction) -> Res {
     check_msg(
@@ -18,32 +25,35 @@ pub async fn list(ctx: &Context, cmd: &ApplicationCommandInteraction) -> Res {
         .expect("Songbird Voice client placed in at initialisation.")
         .clone();
 
+    let lang = i18n::lang(ctx, cmd.guild_id, &cmd.locale).await;
     if let Some(handler_lock) = manager.get(cmd.guild_id.unwrap()) {
         let handler = handler_lock.lock().await;
         let queue = handler.queue().current_queue();
 
         check_msg(
             cmd.edit_original_interaction_response(&ctx.http, |response| {
-                response.content(format!(
-                    "**{} track(s) in queue**\n{} first tracks:",
-                    queue.len(),
-                    10.min(queue.len())
+                response.content(tr(
+                    lang,
+                    "list.header",
+                    &[&queue.len(), &10.min(queue.len())],
                 ));
 
                 queue[..10.min(queue.len())].iter().for_each(|track| {
                     let meta = track.metadata().clone();
                     let mut e = CreateEmbed::default();
                     e.field(
-                        "Title",
-                        &meta
-                            .title
-                            .unwrap_or_else(|| "This shit has no title?".to_string()),
+                        tr(lang, "np.track", &[]),
+                        meta.title.unwrap_or_else(|| tr(lang, "no_title", &[])),
                         false,
                     );
                     if let Some(t) = meta.source_url {
-                        e.field("URL", t, false);
+                        e.field(tr(lang, "np.url", &[]), t, false);
                     }
-                    e.field("Duration", duration_format(meta.duration), true);
+                    e.field(
+                        tr(lang, "np.duration", &[]),
+                        duration_format(meta.duration, lang),
+                        true,
+                    );
                     if let Some(t) = meta.thumbnail {
                         e.thumbnail(t);
                     }
@@ -58,7 +68,7 @@ pub async fn list(ctx: &Context, cmd: &ApplicationCommandInteraction) -> Res {
     } else {
         check_msg(
             cmd.edit_original_interaction_response(&ctx.http, |response| {
-                response.content("Not playing in a voice channel.")
+                response.content(tr(lang, "not_playing", &[]))
             })
             .await,
         );
@@ -66,3 +76,47 @@ pub async fn list(ctx: &Context, cmd: &ApplicationCommandInteraction) -> Res {
 
     Ok(())
 }
+
+/// Suggests queue entries as `3 — Title` for options taking a position.
+pub async fn autocomplete(ctx: &Context, autocomplete: &AutocompleteInteraction) {
+    let manager = songbird::get(ctx)
+        .await
+        .expect("Songbird Voice client placed in at initialisation.")
+        .clone();
+
+    let queue = match autocomplete.guild_id.and_then(|g| manager.get(g)) {
+        Some(handler_lock) => handler_lock.lock().await.queue().current_queue(),
+        None => Vec::new(),
+    };
+
+    let typed = autocomplete_input(autocomplete).to_lowercase();
+    let lang = i18n::lang(ctx, autocomplete.guild_id, &autocomplete.locale).await;
+
+    let choices = queue
+        .iter()
+        .enumerate()
+        // The current track is not up for grabs.
+        .skip(1)
+        .map(|(i, track)| {
+            let title = track
+                .metadata()
+                .title
+                .clone()
+                .unwrap_or_else(|| tr(lang, "no_title", &[]));
+            (i + 1, format!("{} — {}", i + 1, title))
+        })
+        .filter(|(_, name)| name.to_lowercase().contains(&typed))
+        .take(MAX_CHOICES)
+        .collect::<Vec<(usize, String)>>();
+
+    check_msg(
+        autocomplete
+            .create_autocomplete_response(&ctx.http, |response| {
+                choices.iter().for_each(|(position, name)| {
+                    response.add_int_choice(choice_name(name), *position as i64);
+                });
+                response
+            })
+            .await,
+    );
+}
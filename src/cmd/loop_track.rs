@@ -0,0 +1,60 @@
+use crate::cmd::{check_msg, defer_interaction, Invocation, Res};
+use crate::i18n::{self, tr};
+use serenity::{client::Context, prelude::Mentionable};
+use songbird::tracks::LoopState;
+
+pub async fn loop_track(ctx: &Context, cmd: &Invocation<'_>) -> Res {
+    check_msg(
+        cmd.create_interaction_response(&ctx.http, |response| {
+            defer_interaction(response, None, false)
+        })
+        .await,
+    );
+
+    let manager = songbird::get(ctx)
+        .await
+        .expect("Songbird Voice client placed in at initialisation.")
+        .clone();
+
+    let lang = i18n::lang(ctx, cmd.guild_id(), cmd.locale()).await;
+    let current = match manager.get(cmd.guild_id().unwrap()) {
+        Some(handler_lock) => handler_lock.lock().await.queue().current(),
+        None => {
+            check_msg(
+                cmd.edit_original_interaction_response(&ctx.http, |response| {
+                    response.content(tr(lang, "not_playing", &[]))
+                })
+                .await,
+            );
+            return Ok(());
+        }
+    };
+
+    let content = match current {
+        Some(track) => {
+            let looping = matches!(
+                track.get_info().await.map(|info| info.loops),
+                Ok(LoopState::Infinite)
+            );
+
+            match looping {
+                true => {
+                    let _ = track.disable_loop();
+                    tr(lang, "loop.off", &[&cmd.user().mention()])
+                }
+                false => {
+                    let _ = track.enable_loop();
+                    tr(lang, "loop.on", &[&cmd.user().mention()])
+                }
+            }
+        }
+        None => tr(lang, "nothing_playing", &[]),
+    };
+
+    check_msg(
+        cmd.edit_original_interaction_response(&ctx.http, |response| response.content(content))
+            .await,
+    );
+
+    Ok(())
+}
@@ -1,21 +1,306 @@
-use serenity::builder::{CreateInteractionResponse, CreateMessage};
+use crate::i18n::{tr, Lang};
+use serenity::builder::{
+    CreateActionRow, CreateButton, CreateEmbed, CreateInteractionResponse,
+    CreateInteractionResponseFollowup, CreateMessage, EditInteractionResponse,
+};
+use serenity::client::Context;
+use serenity::http::Http;
+use serenity::model::channel::Message;
+use serenity::model::guild::Member;
+use serenity::model::id::{ChannelId, GuildId, UserId};
 use serenity::model::interactions::{
+    application_command::ApplicationCommandInteraction,
+    autocomplete::AutocompleteInteraction,
+    message_component::{ButtonStyle, MessageComponentInteraction},
     InteractionApplicationCommandCallbackDataFlags, InteractionResponseType,
 };
+use serenity::model::user::User;
+use serenity::prelude::TypeMapKey;
 use serenity::Result as SerenityResult;
-use songbird::input::Metadata;
+use songbird::input::{Input, Metadata};
+use songbird::tracks::{create_player, TrackHandle};
+use songbird::Call;
 use std::{error::Error, time::Duration};
 
+pub mod autoplay;
+pub mod clear;
+pub mod config;
+pub mod follow;
+pub mod jingle;
 pub mod join;
+pub mod jump;
 pub mod leave;
 pub mod list;
+pub mod loop_track;
 pub mod play_pause;
 pub mod queue;
+pub mod remove;
+pub mod schedule;
+pub mod sfx;
+pub mod shuffle;
 pub mod skip;
+pub mod sleep;
 pub mod stop;
 
 pub type Res = Result<(), Box<dyn Error>>;
 
+/// Extra data attached to every track going through the queue.
+#[derive(Clone, Copy, Default)]
+pub struct TrackInfo {
+    pub autoplay: bool,
+    /// Who queued the track, unset for tracks queued by the bot itself.
+    pub requester: Option<UserId>,
+}
+
+impl TypeMapKey for TrackInfo {
+    type Value = TrackInfo;
+}
+
+/// Names of the commands registered with Discord, once ready.
+pub struct Registered;
+
+impl TypeMapKey for Registered {
+    type Value = Vec<String>;
+}
+
+pub async fn track_info(track: &TrackHandle) -> TrackInfo {
+    track
+        .typemap()
+        .read()
+        .await
+        .get::<TrackInfo>()
+        .copied()
+        .unwrap_or_default()
+}
+
+pub async fn enqueue(handler: &mut Call, source: Input, info: TrackInfo) -> TrackHandle {
+    let (track, handle) = create_player(source);
+    handle.typemap().write().await.insert::<TrackInfo>(info);
+    handler.enqueue(track);
+    handle
+}
+
+/// What triggered a command: the slash command itself or one of the buttons
+/// standing in for it.
+pub enum Invocation<'a> {
+    Command(&'a ApplicationCommandInteraction),
+    Component(&'a MessageComponentInteraction),
+}
+
+impl Invocation<'_> {
+    pub fn user(&self) -> &User {
+        match self {
+            Self::Command(i) => &i.user,
+            Self::Component(i) => &i.user,
+        }
+    }
+
+    pub fn guild_id(&self) -> Option<GuildId> {
+        match self {
+            Self::Command(i) => i.guild_id,
+            Self::Component(i) => i.guild_id,
+        }
+    }
+
+    pub fn locale(&self) -> &str {
+        match self {
+            Self::Command(i) => &i.locale,
+            Self::Component(i) => &i.locale,
+        }
+    }
+
+    pub fn member(&self) -> Option<&Member> {
+        match self {
+            Self::Command(i) => i.member.as_ref(),
+            Self::Component(i) => i.member.as_ref(),
+        }
+    }
+
+    pub async fn create_interaction_response<F>(
+        &self,
+        http: impl AsRef<Http>,
+        f: F,
+    ) -> SerenityResult<()>
+    where
+        F: FnOnce(&mut CreateInteractionResponse) -> &mut CreateInteractionResponse,
+    {
+        match self {
+            Self::Command(i) => i.create_interaction_response(http, f).await,
+            Self::Component(i) => i.create_interaction_response(http, f).await,
+        }
+    }
+
+    pub async fn edit_original_interaction_response<F>(
+        &self,
+        http: impl AsRef<Http>,
+        f: F,
+    ) -> SerenityResult<Message>
+    where
+        F: FnOnce(&mut EditInteractionResponse) -> &mut EditInteractionResponse,
+    {
+        match self {
+            Self::Command(i) => i.edit_original_interaction_response(http, f).await,
+            Self::Component(i) => i.edit_original_interaction_response(http, f).await,
+        }
+    }
+
+    pub async fn get_interaction_response(
+        &self,
+        http: impl AsRef<Http>,
+    ) -> SerenityResult<Message> {
+        match self {
+            Self::Command(i) => i.get_interaction_response(http).await,
+            Self::Component(i) => i.get_interaction_response(http).await,
+        }
+    }
+
+    pub async fn create_followup_message<'a, F>(
+        &self,
+        http: impl AsRef<Http>,
+        f: F,
+    ) -> SerenityResult<Message>
+    where
+        for<'b> F: FnOnce(
+            &'b mut CreateInteractionResponseFollowup<'a>,
+        ) -> &'b mut CreateInteractionResponseFollowup<'a>,
+    {
+        match self {
+            Self::Command(i) => i.create_followup_message(http, f).await,
+            Self::Component(i) => i.create_followup_message(http, f).await,
+        }
+    }
+}
+
+/// Buttons attached to now playing messages, each one standing in for a
+/// slash command.
+pub enum ControlBtn {
+    PlayPause,
+    Skip,
+    Stop,
+    Loop,
+    Shuffle,
+}
+
+impl ControlBtn {
+    pub fn from_custom_id(id: &str) -> Option<Self> {
+        [
+            Self::PlayPause,
+            Self::Skip,
+            Self::Stop,
+            Self::Loop,
+            Self::Shuffle,
+        ]
+        .into_iter()
+        .find(|b| b.custom_id() == id)
+    }
+
+    /// The slash command the button stands in for.
+    pub fn command(&self) -> &'static str {
+        match self {
+            Self::PlayPause => "pause",
+            Self::Skip => "skip",
+            Self::Stop => "stop",
+            Self::Loop => "loop",
+            Self::Shuffle => "shuffle",
+        }
+    }
+
+    pub fn custom_id(&self) -> &'static str {
+        match self {
+            Self::PlayPause => "control_play_pause",
+            Self::Skip => "control_skip",
+            Self::Stop => "control_stop",
+            Self::Loop => "control_loop",
+            Self::Shuffle => "control_shuffle",
+        }
+    }
+
+    fn button(&self, lang: Lang) -> CreateButton {
+        let mut b = CreateButton::default();
+        b.custom_id(self.custom_id());
+        match self {
+            Self::PlayPause => b.label(tr(lang, "np.play_pause", &[])),
+            Self::Skip => b.label(tr(lang, "np.skip", &[])),
+            Self::Stop => b.label(tr(lang, "np.stop", &[])).style(ButtonStyle::Danger),
+            Self::Loop => b.label(tr(lang, "np.loop", &[])),
+            Self::Shuffle => b.label(tr(lang, "np.shuffle", &[])),
+        };
+        b
+    }
+
+    pub fn action_row(lang: Lang) -> CreateActionRow {
+        let mut ar = CreateActionRow::default();
+        ar.add_button(Self::PlayPause.button(lang));
+        ar.add_button(Self::Skip.button(lang));
+        ar.add_button(Self::Stop.button(lang));
+        ar.add_button(Self::Loop.button(lang));
+        ar.add_button(Self::Shuffle.button(lang));
+        ar
+    }
+}
+
+/// Whether `member` may manage the guild, going by the permissions sent along
+/// with the interaction.
+pub fn manages_guild(member: Option<&Member>) -> bool {
+    member
+        .and_then(|m| m.permissions)
+        .is_some_and(|p| p.manage_guild())
+}
+
+/// Members of the voice channel `channel_id` who are not bots.
+pub async fn listeners(ctx: &Context, channel_id: ChannelId) -> Vec<Member> {
+    match ctx.cache.guild_channel(channel_id).await {
+        Some(channel) => channel
+            .members(&ctx.cache)
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|u| !u.user.bot)
+            .collect(),
+        None => Vec::new(),
+    }
+}
+
+/// Voice channel `user_id` is currently sitting in.
+pub async fn voice_channel_of(
+    ctx: &Context,
+    guild_id: GuildId,
+    user_id: UserId,
+) -> Option<ChannelId> {
+    ctx.cache
+        .guild_field(guild_id, |g| {
+            g.voice_states.get(&user_id).and_then(|v| v.channel_id)
+        })
+        .await
+        .flatten()
+}
+
+/// Discord shows no more autocomplete choices than that.
+pub const MAX_CHOICES: usize = 25;
+
+/// What was typed so far in the option being autocompleted.
+pub fn autocomplete_input(autocomplete: &AutocompleteInteraction) -> String {
+    autocomplete
+        .data
+        .options
+        .iter()
+        .find(|o| o.focused)
+        .and_then(|o| o.value.as_ref())
+        .map(|v| match v.as_str() {
+            Some(s) => s.to_string(),
+            None => v.to_string(),
+        })
+        .unwrap_or_default()
+}
+
+/// Shortens `name` to fit in an autocomplete choice.
+pub fn choice_name(name: &str) -> String {
+    match name.chars().count() > 100 {
+        true => format!("{}…", name.chars().take(99).collect::<String>()),
+        false => name.to_string(),
+    }
+}
+
 pub fn check_msg<T>(result: SerenityResult<T>) {
     if let Err(why) = result {
         println!("Error sending message: {:?}", why);
@@ -56,27 +341,76 @@ pub fn interaction_reply(
         })
 }
 
-pub fn now_playing_embed(m: &mut CreateMessage, np: Metadata) {
-    m.embed(|e| {
-        e.title("Now playing");
-        e.field("Title", np.title.clone().unwrap(), false);
-        if let Some(t) = np.source_url {
-            e.field("URL", t, false);
-        }
-        e.field("Duration", duration_format(np.duration), false);
-        if let Some(t) = np.thumbnail {
-            e.thumbnail(t);
-        }
+pub fn now_playing_embed(m: &mut CreateMessage, np: Metadata, info: TrackInfo, lang: Lang) {
+    m.embed(|e| now_playing(e, &np, info, Duration::default(), lang))
+        .components(|c| c.add_action_row(ControlBtn::action_row(lang)));
+}
 
-        e
-    });
+pub fn now_playing<'a>(
+    e: &'a mut CreateEmbed,
+    np: &Metadata,
+    info: TrackInfo,
+    position: Duration,
+    lang: Lang,
+) -> &'a mut CreateEmbed {
+    e.title(tr(lang, "np.title", &[]));
+    e.field(tr(lang, "np.track", &[]), np.title.clone().unwrap(), false);
+    if let Some(t) = &np.source_url {
+        e.field(tr(lang, "np.url", &[]), t, false);
+    }
+    e.field(
+        tr(lang, "np.duration", &[]),
+        duration_format(np.duration, lang),
+        false,
+    );
+    if let Some(d) = np.duration.filter(|d| *d != Duration::default()) {
+        e.field(
+            tr(lang, "np.progress", &[]),
+            progress_bar(position, d),
+            false,
+        );
+    }
+    if let Some(t) = &np.thumbnail {
+        e.thumbnail(t);
+    }
+    if info.autoplay {
+        e.footer(|f| f.text(tr(lang, "np.autoplay", &[])));
+    }
+
+    e
+}
+
+/// Renders something like `▬▬▬🔘▬▬▬▬▬▬ 1:23 / 3:45`.
+pub fn progress_bar(position: Duration, duration: Duration) -> String {
+    const WIDTH: usize = 20;
+
+    let position = position.min(duration);
+    let done = (position.as_secs_f64() / duration.as_secs_f64() * WIDTH as f64) as usize;
+    let bar = (0..=WIDTH)
+        .map(|i| if i == done { "🔘" } else { "▬" })
+        .collect::<String>();
+
+    format!(
+        "{} {} / {}",
+        bar,
+        clock_format(position),
+        clock_format(duration)
+    )
+}
+
+fn clock_format(d: Duration) -> String {
+    let secs = d.as_secs();
+    match secs >= 3600 {
+        true => format!("{}:{:02}:{:02}", secs / 3600, secs % 3600 / 60, secs % 60),
+        false => format!("{}:{:02}", secs / 60, secs % 60),
+    }
 }
 
-pub fn duration_format(duration: Option<Duration>) -> String {
+pub fn duration_format(duration: Option<Duration>, lang: Lang) -> String {
     if let Some(d) = duration {
         if d != Duration::default() {
             return humantime::format_duration(d).to_string();
         }
     }
-    "Live".to_string()
+    tr(lang, "np.live", &[])
 }
@@ -1,15 +1,16 @@
-use crate::cmd::{check_msg, defer_interaction, Res};
-use serenity::{
-    client::Context, model::interactions::application_command::ApplicationCommandInteraction,
-    prelude::Mentionable,
-};
+use crate::cmd::{check_msg, defer_interaction, Invocation, Res};
+use crate::i18n::{self, tr};
+use serenity::{client::Context, prelude::Mentionable};
+use songbird::tracks::PlayMode;
 
 pub enum Op {
     Pause,
     Resume,
+    /// Pause when playing, resume otherwise.
+    Toggle,
 }
 
-pub async fn play_pause(ctx: &Context, cmd: &ApplicationCommandInteraction, op: Op) -> Res {
+pub async fn play_pause(ctx: &Context, cmd: &Invocation<'_>, op: Op) -> Res {
     check_msg(
         cmd.create_interaction_response(&ctx.http, |response| {
             defer_interaction(response, None, true)
@@ -22,18 +23,30 @@ pub async fn play_pause(ctx: &Context, cmd: &ApplicationCommandInteraction, op:
         .expect("Songbird Voice client placed in at initialisation.")
         .clone();
 
-    if let Some(handler_lock) = manager.get(cmd.guild_id.unwrap()) {
+    let lang = i18n::lang(ctx, cmd.guild_id(), cmd.locale()).await;
+    if let Some(handler_lock) = manager.get(cmd.guild_id().unwrap()) {
         let handler = handler_lock.lock().await;
         let queue = handler.queue();
 
+        let op = match op {
+            Op::Toggle => match queue.current() {
+                Some(track) => match track.get_info().await {
+                    Ok(info) if info.playing == PlayMode::Play => Op::Pause,
+                    _ => Op::Resume,
+                },
+                None => Op::Resume,
+            },
+            op => op,
+        };
+
         let content = match op {
             Op::Pause => {
                 let _ = queue.pause();
-                format!("⏸ {} paused current track", cmd.user.mention())
+                tr(lang, "pause.paused", &[&cmd.user().mention()])
             }
-            Op::Resume => {
+            _ => {
                 let _ = queue.resume();
-                format!("▶️ {} resumed current track", cmd.user.mention())
+                tr(lang, "pause.resumed", &[&cmd.user().mention()])
             }
         };
 
@@ -44,7 +57,7 @@ pub async fn play_pause(ctx: &Context, cmd: &ApplicationCommandInteraction, op:
     } else {
         check_msg(
             cmd.edit_original_interaction_response(&ctx.http, |response| {
-                response.content("Not playing in a voice channel.")
+                response.content(tr(lang, "not_playing", &[]))
             })
             .await,
         );
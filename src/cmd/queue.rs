@@ -1,12 +1,24 @@
-use crate::cmd::{check_msg, defer_interaction, Res};
+use crate::cmd::join::join_channel;
+use crate::cmd::{
+    autocomplete_input, check_msg, choice_name, defer_interaction, enqueue, voice_channel_of, Res,
+    TrackInfo, MAX_CHOICES,
+};
+use crate::i18n::{self, tr};
+use crate::{history, stage};
 use serenity::{
     client::Context,
     model::gateway::Activity,
-    model::interactions::application_command::{
-        ApplicationCommandInteraction, ApplicationCommandInteractionDataOptionValue,
+    model::id::ChannelId,
+    model::interactions::{
+        application_command::{
+            ApplicationCommandInteraction, ApplicationCommandInteractionDataOptionValue,
+        },
+        autocomplete::AutocompleteInteraction,
     },
 };
-use songbird::input::Restartable;
+use songbird::input::{error::Error as InputError, Restartable};
+use songbird::tracks::TrackHandle;
+use songbird::Call;
 
 pub async fn queue(ctx: &Context, cmd: &ApplicationCommandInteraction) -> Res {
     check_msg(
@@ -16,10 +28,11 @@ pub async fn queue(ctx: &Context, cmd: &ApplicationCommandInteraction) -> Res {
         .await,
     );
 
+    let lang = i18n::lang(ctx, cmd.guild_id, &cmd.locale).await;
     let url_option = cmd
         .data
         .options
-        .get(0)
+        .first()
         .expect("Expected user option")
         .resolved
         .as_ref()
@@ -30,7 +43,7 @@ pub async fn queue(ctx: &Context, cmd: &ApplicationCommandInteraction) -> Res {
         _ => {
             check_msg(
                 cmd.edit_original_interaction_response(&ctx.http, |response| {
-                    response.content("Must provide a URL to a video or audio")
+                    response.content(tr(lang, "queue.no_url", &[]))
                 })
                 .await,
             );
@@ -41,7 +54,7 @@ pub async fn queue(ctx: &Context, cmd: &ApplicationCommandInteraction) -> Res {
     if !url.starts_with("http") {
         check_msg(
             cmd.edit_original_interaction_response(&ctx.http, |response| {
-                response.content("Must provide a valid URL")
+                response.content(tr(lang, "invalid_url", &[]))
             })
             .await,
         );
@@ -55,18 +68,32 @@ pub async fn queue(ctx: &Context, cmd: &ApplicationCommandInteraction) -> Res {
 
     let guild_id = cmd.guild_id.unwrap();
 
-    if let Some(handler_lock) = manager.get(guild_id) {
+    // Follow the caller into their voice channel when not playing anywhere yet.
+    let handler_lock = match manager.get(guild_id) {
+        Some(handler_lock) => Some(handler_lock),
+        None => match voice_channel_of(ctx, guild_id, cmd.user.id).await {
+            Some(channel_id) => join_channel(ctx, guild_id, channel_id, cmd.channel_id)
+                .await
+                .ok(),
+            None => None,
+        },
+    };
+
+    if let Some(handler_lock) = handler_lock {
         let mut handler = handler_lock.lock().await;
 
-        // Here, we use lazy restartable sources to make sure that we don't pay
-        // for decoding, playback on tracks which aren't actually live yet.
-        let source = match Restartable::ytdl(url, true).await {
-            Ok(source) => source,
+        let info = TrackInfo {
+            requester: Some(cmd.user.id),
+            ..Default::default()
+        };
+
+        let track = match enqueue_url(&mut handler, url, info).await {
+            Ok(track) => track,
             Err(why) => {
                 println!("Err starting source: {:?}", why);
                 check_msg(
                     cmd.edit_original_interaction_response(&ctx.http, |response| {
-                        response.content("Error sourcing ffmpeg")
+                        response.content(tr(lang, "ffmpeg_error", &[]))
                     })
                     .await,
                 );
@@ -74,36 +101,25 @@ pub async fn queue(ctx: &Context, cmd: &ApplicationCommandInteraction) -> Res {
             }
         };
 
-        handler.enqueue_source(source.into());
-
-        let title = handler
-            .queue()
-            .current_queue()
-            .last()
-            .unwrap()
-            .metadata()
-            .title
-            .clone()
-            .unwrap();
+        let title = track.metadata().title.clone().unwrap();
 
         check_msg(
             cmd.edit_original_interaction_response(&ctx.http, |response| {
-                response.content(format!(
-                    "Queued **{}** at position {}",
-                    &title,
-                    handler.queue().len()
-                ))
+                response.content(tr(lang, "queue.queued", &[&title, &handler.queue().len()]))
             })
             .await,
         );
 
         if handler.queue().len() == 1 {
             ctx.set_activity(Activity::listening(&title)).await;
+            if let Some(channel) = handler.current_channel() {
+                stage::topic(ctx, ChannelId(channel.0), &title).await;
+            }
         }
     } else {
         check_msg(
             cmd.edit_original_interaction_response(&ctx.http, |response| {
-                response.content("Not in a voice channel to play in")
+                response.content(tr(lang, "nowhere_to_play", &[]))
             })
             .await,
         );
@@ -111,3 +127,46 @@ pub async fn queue(ctx: &Context, cmd: &ApplicationCommandInteraction) -> Res {
 
     Ok(())
 }
+
+/// Sources `url` and adds it at the end of the queue.
+pub async fn enqueue_url(
+    handler: &mut Call,
+    url: String,
+    info: TrackInfo,
+) -> Result<TrackHandle, InputError> {
+    // Here, we use lazy restartable sources to make sure that we don't pay
+    // for decoding, playback on tracks which aren't actually live yet.
+    let source = Restartable::ytdl(url, true).await?;
+
+    Ok(enqueue(handler, source.into(), info).await)
+}
+
+/// Suggests recently played tracks matching what was typed so far.
+pub async fn autocomplete(ctx: &Context, autocomplete: &AutocompleteInteraction) {
+    let played = match autocomplete.guild_id {
+        Some(guild_id) => history::recent(ctx, guild_id).await,
+        None => Vec::new(),
+    };
+
+    let typed = autocomplete_input(autocomplete).to_lowercase();
+
+    let choices = played
+        .into_iter()
+        // Longer urls would not fit in a choice.
+        .filter(|p| p.url.len() <= 100)
+        .filter_map(|p| Some((p.title?, p.url)))
+        .filter(|(title, url)| title.to_lowercase().contains(&typed) || url.contains(&typed))
+        .take(MAX_CHOICES)
+        .collect::<Vec<(String, String)>>();
+
+    check_msg(
+        autocomplete
+            .create_autocomplete_response(&ctx.http, |response| {
+                choices.iter().for_each(|(title, url)| {
+                    response.add_string_choice(choice_name(title), url);
+                });
+                response
+            })
+            .await,
+    );
+}
@@ -0,0 +1,145 @@
+use crate::cmd::{check_msg, defer_interaction, Invocation, Res};
+use crate::dj::Access;
+use crate::i18n::{self, tr};
+use crate::vote::{self, Motion};
+use serenity::{
+    client::Context,
+    model::interactions::application_command::{
+        ApplicationCommandInteraction, ApplicationCommandInteractionDataOptionValue,
+    },
+    prelude::TypeMapKey,
+};
+use songbird::tracks::{TrackHandle, TrackQueue};
+
+/// Marks tracks taken out of the queue before their turn came.
+pub struct Removed;
+
+impl TypeMapKey for Removed {
+    type Value = ();
+}
+
+pub async fn is_removed(track: &TrackHandle) -> bool {
+    track.typemap().read().await.contains_key::<Removed>()
+}
+
+/// Stops playback and empties the queue. Stopped tracks count as removed, so
+/// they neither go in the history nor set autoplay off.
+pub async fn stop_queue(queue: &TrackQueue) {
+    let drained =
+        queue.modify_queue(|queue| queue.drain(..).map(|q| q.handle()).collect::<Vec<_>>());
+    for track in drained.iter() {
+        track.typemap().write().await.insert::<Removed>(());
+        let _ = track.stop();
+    }
+}
+
+pub async fn remove(ctx: &Context, cmd: &ApplicationCommandInteraction, access: Access) -> Res {
+    check_msg(
+        cmd.create_interaction_response(&ctx.http, |response| {
+            defer_interaction(response, None, false)
+        })
+        .await,
+    );
+
+    let position = match cmd.data.options.first().and_then(|o| o.resolved.as_ref()) {
+        Some(ApplicationCommandInteractionDataOptionValue::Integer(position)) => *position,
+        _ => 0,
+    };
+
+    let manager = songbird::get(ctx)
+        .await
+        .expect("Songbird Voice client placed in at initialisation.")
+        .clone();
+
+    let lang = i18n::lang(ctx, cmd.guild_id, &cmd.locale).await;
+    let track = match manager.get(cmd.guild_id.unwrap()) {
+        Some(handler_lock) => {
+            let queue = handler_lock.lock().await.queue().current_queue();
+            // Positions start at 2, the current track is not up for removal.
+            match position {
+                p if p >= 2 => queue.get(p as usize - 1).cloned(),
+                _ => None,
+            }
+        }
+        None => {
+            check_msg(
+                cmd.edit_original_interaction_response(&ctx.http, |response| {
+                    response.content(tr(lang, "not_playing", &[]))
+                })
+                .await,
+            );
+            return Ok(());
+        }
+    };
+
+    match track {
+        Some(track) => {
+            let cmd = Invocation::Command(cmd);
+            match access {
+                Access::Dj => vote::bypass(ctx, &cmd, Motion::Remove(track), "vote.by_dj").await,
+                Access::Open | Access::Vote => vote::hold(ctx, &cmd, Motion::Remove(track)).await,
+            }
+        }
+        None => {
+            check_msg(
+                cmd.edit_original_interaction_response(&ctx.http, |response| {
+                    response.content(tr(lang, "no_track_at", &[&position]))
+                })
+                .await,
+            );
+            Ok(())
+        }
+    }
+}
+
+/// Takes `track` out of the queue unless it is already playing, returns
+/// whether it was still waiting there.
+pub async fn remove_track(queue: &TrackQueue, track: &TrackHandle) -> bool {
+    let index = queue
+        .current_queue()
+        .iter()
+        .skip(1)
+        .position(|t| t.uuid() == track.uuid());
+
+    let removed = match index {
+        Some(index) => queue.dequeue(index + 1),
+        None => None,
+    };
+
+    match removed {
+        Some(removed) => {
+            track.typemap().write().await.insert::<Removed>(());
+            let _ = removed.stop();
+            true
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use songbird::input::{reader::Reader, Input};
+    use songbird::Driver;
+
+    fn silence() -> Input {
+        Input::float_pcm(false, Reader::from_memory(vec![0; 4 * 48_000]))
+    }
+
+    #[tokio::test]
+    async fn stopping_marks_every_track_removed() {
+        let mut driver = Driver::default();
+        let queue = TrackQueue::new();
+        queue.add_source(silence(), &mut driver);
+        queue.add_source(silence(), &mut driver);
+        let tracks = queue.current_queue();
+
+        stop_queue(&queue).await;
+
+        assert!(queue.is_empty());
+        assert_eq!(tracks.len(), 2);
+        for track in tracks.iter() {
+            assert!(is_removed(track).await);
+        }
+    }
+}
@@ -0,0 +1,414 @@
+use crate::cmd::join::join_channel;
+use crate::cmd::queue::enqueue_url;
+use crate::cmd::{check_msg, defer_interaction, manages_guild, Res, TrackInfo};
+use crate::i18n::{self, tr, Lang};
+use crate::{announce, stage, ytdl};
+use serde::{Deserialize, Serialize};
+use serenity::{
+    client::Context,
+    model::channel::ChannelType,
+    model::gateway::Activity,
+    model::id::{ChannelId, GuildId},
+    model::interactions::application_command::{
+        ApplicationCommandInteraction, ApplicationCommandInteractionDataOption,
+        ApplicationCommandInteractionDataOptionValue,
+    },
+    prelude::{Mentionable, TypeMapKey},
+};
+use std::{
+    fs,
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tokio::sync::Mutex;
+
+pub const DAYS: [&str; 7] = [
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+    "Sunday",
+];
+
+/// Most tracks queued from a scheduled playlist.
+const PLAYLIST_LIMIT: usize = 50;
+
+#[derive(Clone, Serialize, Deserialize)]
+struct Schedule {
+    id: u64,
+    guild_id: u64,
+    channel_id: u64,
+    announce_id: u64,
+    /// Day of the week starting from Monday, every day if unset.
+    day: Option<u8>,
+    hour: u8,
+    minute: u8,
+    url: String,
+    /// Who added it, unknown for schedules older than this field.
+    #[serde(default)]
+    creator: u64,
+}
+
+impl Schedule {
+    fn is_due(&self, now: &Now) -> bool {
+        self.day.is_none_or(|d| d == now.day) && self.hour == now.hour && self.minute == now.minute
+    }
+
+    fn describe(&self, lang: Lang) -> String {
+        let day = match self.day {
+            Some(d) => tr(lang, &format!("schedule.every.{}", d), &[]),
+            None => tr(lang, "schedule.every_day", &[]),
+        };
+        tr(
+            lang,
+            "schedule.entry",
+            &[
+                &self.id,
+                &day,
+                &format!("{:02}:{:02}", self.hour, self.minute),
+                &ChannelId(self.channel_id).mention(),
+                &self.url,
+            ],
+        )
+    }
+}
+
+pub struct Schedules {
+    path: PathBuf,
+    entries: Vec<Schedule>,
+    running: bool,
+}
+
+impl TypeMapKey for Schedules {
+    type Value = Arc<Mutex<Schedules>>;
+}
+
+impl Schedules {
+    pub fn load(path: PathBuf) -> Self {
+        let entries = match fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|why| {
+                println!("Err parsing schedules {:?}: {:?}", path, why);
+                Vec::new()
+            }),
+            Err(_) => Vec::new(),
+        };
+
+        Self {
+            path,
+            entries,
+            running: false,
+        }
+    }
+
+    fn save(&self) {
+        if let Some(dir) = self.path.parent() {
+            let _ = fs::create_dir_all(dir);
+        }
+
+        let content = serde_json::to_string_pretty(&self.entries).unwrap();
+        if let Err(why) = fs::write(&self.path, content) {
+            println!("Err saving schedules {:?}: {:?}", self.path, why);
+        }
+    }
+}
+
+struct Now {
+    /// Minutes since the epoch.
+    epoch: u64,
+    day: u8,
+    hour: u8,
+    minute: u8,
+}
+
+impl Now {
+    fn utc() -> Self {
+        let secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        Self {
+            epoch: secs / 60,
+            // The epoch was on a Thursday.
+            day: ((secs / 86400 + 3) % 7) as u8,
+            hour: (secs % 86400 / 3600) as u8,
+            minute: (secs % 3600 / 60) as u8,
+        }
+    }
+}
+
+pub async fn schedule(ctx: &Context, cmd: &ApplicationCommandInteraction) -> Res {
+    let sub = cmd.data.options.first().expect("Expected subcommand");
+
+    check_msg(
+        cmd.create_interaction_response(&ctx.http, |response| {
+            defer_interaction(response, None, sub.name == "list")
+        })
+        .await,
+    );
+
+    let guild_id = cmd.guild_id.unwrap();
+    let lang = i18n::lang(ctx, cmd.guild_id, &cmd.locale).await;
+
+    // Not holding on to schedules while talking to Discord.
+    let content = {
+        let data = ctx.data.read().await;
+        let mut schedules = data
+            .get::<Schedules>()
+            .expect("Schedules placed in at initialisation.")
+            .lock()
+            .await;
+
+        match sub.name.as_str() {
+            // The bot would join wherever it is told every day.
+            "add" if !manages_guild(cmd.member.as_ref()) => {
+                tr(lang, "managers.only", &[&"schedule add"])
+            }
+            "add" => match parse_schedule(sub) {
+                Ok(mut schedule) => {
+                    schedule.id = schedules.entries.iter().map(|s| s.id).max().unwrap_or(0) + 1;
+                    schedule.guild_id = guild_id.0;
+                    schedule.announce_id = cmd.channel_id.0;
+                    schedule.creator = cmd.user.id.0;
+
+                    let content = tr(
+                        lang,
+                        "schedule.added",
+                        &[&cmd.user.mention(), &schedule.describe(lang)],
+                    );
+                    schedules.entries.push(schedule);
+                    schedules.save();
+                    content
+                }
+                Err(key) => tr(lang, key, &[]),
+            },
+            "cancel" => {
+                let id = match option(sub, "id") {
+                    Some(ApplicationCommandInteractionDataOptionValue::Integer(id)) => *id as u64,
+                    _ => 0,
+                };
+
+                let index = schedules
+                    .entries
+                    .iter()
+                    .position(|s| s.id == id && s.guild_id == guild_id.0);
+
+                match index {
+                    Some(index)
+                        if schedules.entries[index].creator == cmd.user.id.0
+                            || manages_guild(cmd.member.as_ref()) =>
+                    {
+                        schedules.entries.remove(index);
+                        schedules.save();
+                        tr(lang, "schedule.cancelled", &[&cmd.user.mention(), &id])
+                    }
+                    Some(_) => tr(lang, "schedule.not_yours", &[&id]),
+                    None => tr(lang, "schedule.unknown", &[&id]),
+                }
+            }
+            _ => {
+                let list = schedules
+                    .entries
+                    .iter()
+                    .filter(|s| s.guild_id == guild_id.0)
+                    .map(|s| s.describe(lang))
+                    .collect::<Vec<String>>();
+
+                match list.is_empty() {
+                    true => tr(lang, "schedule.none", &[]),
+                    false => tr(lang, "schedule.list", &[&list.len(), &list.join("\n")]),
+                }
+            }
+        }
+    };
+
+    check_msg(
+        cmd.edit_original_interaction_response(&ctx.http, |response| response.content(content))
+            .await,
+    );
+
+    Ok(())
+}
+
+fn option<'a>(
+    sub: &'a ApplicationCommandInteractionDataOption,
+    name: &str,
+) -> Option<&'a ApplicationCommandInteractionDataOptionValue> {
+    sub.options
+        .iter()
+        .find(|o| o.name == name)
+        .and_then(|o| o.resolved.as_ref())
+}
+
+/// Reads the schedule out of `sub`, errors being catalog keys.
+fn parse_schedule(sub: &ApplicationCommandInteractionDataOption) -> Result<Schedule, &'static str> {
+    let channel_id = match option(sub, "channel") {
+        Some(ApplicationCommandInteractionDataOptionValue::Channel(channel))
+            if matches!(channel.kind, ChannelType::Voice | ChannelType::Stage) =>
+        {
+            channel.id.0
+        }
+        _ => return Err("not_voice_channel"),
+    };
+
+    let (hour, minute) = match option(sub, "time") {
+        Some(ApplicationCommandInteractionDataOptionValue::String(time)) => time
+            .trim()
+            .split_once(':')
+            .and_then(|(h, m)| Some((h.parse::<u8>().ok()?, m.parse::<u8>().ok()?)))
+            .filter(|(h, m)| *h < 24 && *m < 60)
+            .ok_or("schedule.invalid_time")?,
+        _ => return Err("schedule.invalid_time"),
+    };
+
+    let url = match option(sub, "url") {
+        Some(ApplicationCommandInteractionDataOptionValue::String(url))
+            if url.starts_with("http") =>
+        {
+            url.clone()
+        }
+        _ => return Err("invalid_url"),
+    };
+
+    let day = match option(sub, "day") {
+        Some(ApplicationCommandInteractionDataOptionValue::String(day)) => {
+            DAYS.iter().position(|d| d == day).map(|d| d as u8)
+        }
+        _ => None,
+    };
+
+    Ok(Schedule {
+        id: 0,
+        guild_id: 0,
+        channel_id,
+        announce_id: 0,
+        day,
+        hour,
+        minute,
+        url,
+        creator: 0,
+    })
+}
+
+/// Starts checking schedules every so often, unless it is already running.
+pub async fn start(ctx: &Context) {
+    {
+        let data = ctx.data.read().await;
+        let mut schedules = data
+            .get::<Schedules>()
+            .expect("Schedules placed in at initialisation.")
+            .lock()
+            .await;
+
+        if schedules.running {
+            return;
+        }
+        schedules.running = true;
+    }
+
+    let ctx = ctx.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(20));
+        let mut last = 0;
+
+        loop {
+            interval.tick().await;
+
+            let now = Now::utc();
+            if now.epoch == last {
+                continue;
+            }
+            last = now.epoch;
+
+            let due = {
+                let data = ctx.data.read().await;
+                let schedules = data
+                    .get::<Schedules>()
+                    .expect("Schedules placed in at initialisation.")
+                    .lock()
+                    .await;
+
+                schedules
+                    .entries
+                    .iter()
+                    .filter(|s| s.is_due(&now))
+                    .cloned()
+                    .collect::<Vec<Schedule>>()
+            };
+
+            for schedule in due {
+                let ctx = ctx.clone();
+                tokio::spawn(async move { run(&ctx, schedule).await });
+            }
+        }
+    });
+}
+
+async fn run(ctx: &Context, schedule: Schedule) {
+    let guild_id = GuildId(schedule.guild_id);
+    let channel_id = ChannelId(schedule.channel_id);
+    let chan_id = ChannelId(schedule.announce_id);
+    let lang = i18n::guild_lang(ctx, guild_id).await;
+
+    let handler_lock = match join_channel(ctx, guild_id, channel_id, chan_id).await {
+        Ok(handler_lock) => handler_lock,
+        Err(why) => {
+            println!("Err joining for schedule #{}: {:?}", schedule.id, why);
+            if let Some(chan_id) = announce::target(ctx, guild_id, Some(chan_id)).await {
+                check_msg(
+                    chan_id
+                        .say(
+                            &ctx.http,
+                            tr(
+                                lang,
+                                "schedule.join_failed",
+                                &[&schedule.id, &channel_id.mention()],
+                            ),
+                        )
+                        .await,
+                );
+            }
+            return;
+        }
+    };
+
+    let mut urls = ytdl::flat_playlist(&schedule.url, PLAYLIST_LIMIT)
+        .await
+        .iter()
+        .map(|entry| entry.url())
+        .collect::<Vec<String>>();
+    if urls.is_empty() {
+        urls.push(schedule.url.clone());
+    }
+
+    let mut queued = 0;
+    for url in urls {
+        let mut handler = handler_lock.lock().await;
+        match enqueue_url(&mut handler, url, TrackInfo::default()).await {
+            Ok(track) => {
+                queued += 1;
+                if handler.queue().len() == 1 {
+                    if let Some(title) = &track.metadata().title {
+                        ctx.set_activity(Activity::listening(title)).await;
+                        stage::topic(ctx, channel_id, title).await;
+                    }
+                }
+            }
+            Err(why) => println!("Err starting source: {:?}", why),
+        }
+    }
+
+    announce::say(
+        ctx,
+        guild_id,
+        tr(
+            lang,
+            "schedule.queued",
+            &[&schedule.id, &queued, &channel_id.mention()],
+        ),
+    )
+    .await;
+}
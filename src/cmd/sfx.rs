@@ -0,0 +1,140 @@
+use crate::cmd::{autocomplete_input, check_msg, defer_interaction, Res, MAX_CHOICES};
+use crate::i18n::{self, tr};
+use crate::{mix, settings};
+use serenity::{
+    client::Context,
+    model::id::GuildId,
+    model::interactions::{
+        application_command::{
+            ApplicationCommandInteraction, ApplicationCommandInteractionDataOptionValue,
+        },
+        autocomplete::AutocompleteInteraction,
+    },
+    prelude::Mentionable,
+};
+use std::{fs, path::PathBuf};
+
+const EXTENSIONS: [&str; 6] = ["mp3", "ogg", "opus", "wav", "flac", "m4a"];
+
+pub async fn sfx(ctx: &Context, cmd: &ApplicationCommandInteraction) -> Res {
+    check_msg(
+        cmd.create_interaction_response(&ctx.http, |response| {
+            defer_interaction(response, None, false)
+        })
+        .await,
+    );
+
+    let guild_id = cmd.guild_id.unwrap();
+    let lang = i18n::lang(ctx, cmd.guild_id, &cmd.locale).await;
+
+    let name = match cmd.data.options.first().and_then(|o| o.resolved.as_ref()) {
+        Some(ApplicationCommandInteractionDataOptionValue::String(name)) => name.clone(),
+        _ => String::new(),
+    };
+
+    let path = match clips(ctx, guild_id)
+        .await
+        .into_iter()
+        .find(|(n, _)| *n == name)
+    {
+        Some((_, path)) => path,
+        None => {
+            check_msg(
+                cmd.edit_original_interaction_response(&ctx.http, |response| {
+                    response.content(tr(lang, "sfx.unknown", &[&name]))
+                })
+                .await,
+            );
+            return Ok(());
+        }
+    };
+
+    let manager = songbird::get(ctx)
+        .await
+        .expect("Songbird Voice client placed in at initialisation.")
+        .clone();
+
+    let handler_lock = match manager.get(guild_id) {
+        Some(handler_lock) => handler_lock,
+        None => {
+            check_msg(
+                cmd.edit_original_interaction_response(&ctx.http, |response| {
+                    response.content(tr(lang, "nowhere_to_play", &[]))
+                })
+                .await,
+            );
+            return Ok(());
+        }
+    };
+
+    let content = match songbird::ffmpeg(&path).await {
+        Ok(source) => {
+            mix::play_over(ctx, guild_id, &handler_lock, source, 1.0).await;
+            tr(lang, "sfx.played", &[&cmd.user.mention(), &name])
+        }
+        Err(why) => {
+            println!("Err starting clip {:?}: {:?}", path, why);
+            tr(lang, "ffmpeg_error", &[])
+        }
+    };
+
+    check_msg(
+        cmd.edit_original_interaction_response(&ctx.http, |response| response.content(content))
+            .await,
+    );
+
+    Ok(())
+}
+
+/// Suggests clip names matching what was typed so far.
+pub async fn autocomplete(ctx: &Context, autocomplete: &AutocompleteInteraction) {
+    let guild_id = match autocomplete.guild_id {
+        Some(guild_id) => guild_id,
+        None => return,
+    };
+
+    let typed = autocomplete_input(autocomplete).to_lowercase();
+
+    let names = clips(ctx, guild_id)
+        .await
+        .into_iter()
+        .map(|(name, _)| name)
+        .filter(|name| name.to_lowercase().contains(&typed))
+        .take(MAX_CHOICES)
+        .collect::<Vec<String>>();
+
+    check_msg(
+        autocomplete
+            .create_autocomplete_response(&ctx.http, |response| {
+                names.iter().for_each(|name| {
+                    response.add_string_choice(name, name);
+                });
+                response
+            })
+            .await,
+    );
+}
+
+/// Clips available in a guild with their names, sorted by name.
+async fn clips(ctx: &Context, guild_id: GuildId) -> Vec<(String, PathBuf)> {
+    let dir = settings::data_dir(ctx)
+        .await
+        .join("sfx")
+        .join(guild_id.to_string());
+
+    let mut clips = match fs::read_dir(&dir) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| {
+                path.extension()
+                    .and_then(|e| e.to_str())
+                    .is_some_and(|e| EXTENSIONS.contains(&e.to_lowercase().as_str()))
+            })
+            .filter_map(|path| Some((path.file_stem()?.to_str()?.to_string(), path.clone())))
+            .collect::<Vec<(String, PathBuf)>>(),
+        Err(_) => Vec::new(),
+    };
+
+    clips.sort();
+    clips
+}
@@ -0,0 +1,32 @@
+use crate::cmd::{check_msg, defer_interaction, Invocation, Res};
+use crate::dj::Access;
+use crate::vote::{self, Motion};
+use rand::seq::SliceRandom;
+use serenity::client::Context;
+use songbird::tracks::TrackQueue;
+
+pub async fn shuffle(ctx: &Context, cmd: &Invocation<'_>, access: Access) -> Res {
+    check_msg(
+        cmd.create_interaction_response(&ctx.http, |response| {
+            defer_interaction(response, None, false)
+        })
+        .await,
+    );
+
+    match access {
+        Access::Dj => vote::bypass(ctx, cmd, Motion::Shuffle, "vote.by_dj").await,
+        Access::Open | Access::Vote => vote::hold(ctx, cmd, Motion::Shuffle).await,
+    }
+}
+
+/// Shuffles what comes after the current track, which keeps playing.
+/// Returns whether there was enough to shuffle.
+pub fn shuffle_upcoming(queue: &TrackQueue) -> bool {
+    queue.modify_queue(|queue| {
+        if queue.len() < 3 {
+            return false;
+        }
+        queue.make_contiguous()[1..].shuffle(&mut rand::thread_rng());
+        true
+    })
+}
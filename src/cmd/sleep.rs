@@ -0,0 +1,290 @@
+use crate::cmd::{check_msg, defer_interaction, leave, Res};
+use crate::i18n::{self, tr, Lang};
+use serenity::{
+    client::Context,
+    model::id::GuildId,
+    model::interactions::application_command::{
+        ApplicationCommandInteraction, ApplicationCommandInteractionDataOptionValue,
+    },
+    prelude::{Mentionable, TypeMapKey},
+};
+use songbird::tracks::TrackHandle;
+use std::{collections::HashMap, sync::Arc, time::Duration};
+use tokio::{sync::Mutex, task::JoinHandle, time::Instant};
+
+enum After {
+    Duration(Instant),
+    EndOfTrack,
+    EndOfQueue,
+}
+
+pub struct Timer {
+    after: After,
+    task: Option<JoinHandle<()>>,
+}
+
+pub struct SleepTimers;
+
+impl TypeMapKey for SleepTimers {
+    type Value = Arc<Mutex<HashMap<GuildId, Timer>>>;
+}
+
+pub async fn sleep(ctx: &Context, cmd: &ApplicationCommandInteraction) -> Res {
+    let sub = cmd.data.options.first().expect("Expected subcommand");
+
+    check_msg(
+        cmd.create_interaction_response(&ctx.http, |response| {
+            defer_interaction(response, None, sub.name == "status")
+        })
+        .await,
+    );
+
+    let guild_id = cmd.guild_id.unwrap();
+    let lang = i18n::lang(ctx, cmd.guild_id, &cmd.locale).await;
+
+    let content = match sub.name.as_str() {
+        "set" => {
+            let value = match sub.options.first().and_then(|o| o.resolved.as_ref()) {
+                Some(ApplicationCommandInteractionDataOptionValue::String(value)) => value.clone(),
+                _ => String::new(),
+            };
+
+            let manager = songbird::get(ctx)
+                .await
+                .expect("Songbird Voice client placed in at initialisation.")
+                .clone();
+
+            match (manager.get(guild_id), parse_after(&value)) {
+                (None, _) => tr(lang, "not_playing", &[]),
+                (Some(_), None) => tr(lang, "sleep.invalid", &[]),
+                (Some(_), Some(after)) => {
+                    let description = describe(&after, lang);
+                    set(ctx, guild_id, after).await;
+                    tr(lang, "sleep.set", &[&cmd.user.mention(), &description])
+                }
+            }
+        }
+        "cancel" => match cancel(ctx, guild_id).await {
+            true => tr(lang, "sleep.cancelled", &[&cmd.user.mention()]),
+            false => tr(lang, "sleep.nothing_to_cancel", &[]),
+        },
+        _ => status(ctx, guild_id, lang).await,
+    };
+
+    check_msg(
+        cmd.edit_original_interaction_response(&ctx.http, |response| response.content(content))
+            .await,
+    );
+
+    Ok(())
+}
+
+fn parse_after(value: &str) -> Option<After> {
+    match value.trim() {
+        "end-of-track" => Some(After::EndOfTrack),
+        "end-of-queue" => Some(After::EndOfQueue),
+        // Too far out to be a point in time is no valid duration either.
+        v => humantime::parse_duration(v)
+            .ok()
+            .and_then(|d| Instant::now().checked_add(d))
+            .map(After::Duration),
+    }
+}
+
+fn describe(after: &After, lang: Lang) -> String {
+    match after {
+        After::Duration(deadline) => tr(
+            lang,
+            "sleep.for",
+            &[&humantime::format_duration(round(
+                deadline.saturating_duration_since(Instant::now()),
+            ))],
+        ),
+        After::EndOfTrack => tr(lang, "sleep.for_track", &[]),
+        After::EndOfQueue => tr(lang, "sleep.for_queue", &[]),
+    }
+}
+
+async fn set(ctx: &Context, guild_id: GuildId, after: After) {
+    let task = match after {
+        After::Duration(deadline) => {
+            let ctx = ctx.clone();
+            Some(tokio::spawn(async move {
+                tokio::time::sleep_until(deadline).await;
+                fall_asleep(&ctx, guild_id).await;
+            }))
+        }
+        _ => None,
+    };
+
+    let data = ctx.data.read().await;
+    let mut timers = data
+        .get::<SleepTimers>()
+        .expect("SleepTimers placed in at initialisation.")
+        .lock()
+        .await;
+
+    if let Some(Timer {
+        task: Some(previous),
+        ..
+    }) = timers.insert(guild_id, Timer { after, task })
+    {
+        previous.abort();
+    }
+}
+
+/// Drops the guild's sleep timer, returns whether there was one.
+pub async fn cancel(ctx: &Context, guild_id: GuildId) -> bool {
+    let data = ctx.data.read().await;
+    let mut timers = data
+        .get::<SleepTimers>()
+        .expect("SleepTimers placed in at initialisation.")
+        .lock()
+        .await;
+
+    match timers.remove(&guild_id) {
+        Some(timer) => {
+            if let Some(task) = timer.task {
+                task.abort();
+            }
+            true
+        }
+        None => false,
+    }
+}
+
+async fn status(ctx: &Context, guild_id: GuildId, lang: Lang) -> String {
+    let data = ctx.data.read().await;
+    let timers = data
+        .get::<SleepTimers>()
+        .expect("SleepTimers placed in at initialisation.")
+        .lock()
+        .await;
+
+    let after = match timers.get(&guild_id) {
+        Some(timer) => &timer.after,
+        None => return tr(lang, "sleep.none", &[]),
+    };
+
+    let left = match after {
+        After::Duration(deadline) => Some(deadline.saturating_duration_since(Instant::now())),
+        _ => {
+            let manager = songbird::get(ctx)
+                .await
+                .expect("Songbird Voice client placed in at initialisation.")
+                .clone();
+
+            match manager.get(guild_id) {
+                Some(handler_lock) => {
+                    let tracks = handler_lock.lock().await.queue().current_queue();
+                    match after {
+                        After::EndOfTrack => match tracks.first() {
+                            Some(track) => remaining(track).await,
+                            None => Some(Duration::default()),
+                        },
+                        _ => {
+                            let mut total = Some(Duration::default());
+                            for track in tracks.iter() {
+                                total = total.zip(remaining(track).await).map(|(t, r)| t + r);
+                            }
+                            total
+                        }
+                    }
+                }
+                None => Some(Duration::default()),
+            }
+        }
+    };
+
+    let left = match left {
+        Some(left) => humantime::format_duration(round(left)).to_string(),
+        None => tr(lang, "sleep.live", &[]),
+    };
+
+    match after {
+        After::Duration(_) => tr(lang, "sleep.in", &[&left]),
+        After::EndOfTrack => tr(lang, "sleep.after_track", &[&left]),
+        After::EndOfQueue => tr(lang, "sleep.after_queue", &[&left]),
+    }
+}
+
+/// Time left to play `track`, `None` for live tracks.
+async fn remaining(track: &TrackHandle) -> Option<Duration> {
+    let duration = track
+        .metadata()
+        .duration
+        .filter(|d| *d != Duration::default())?;
+    let position = match track.get_info().await {
+        Ok(info) => info.position,
+        Err(_) => Duration::default(),
+    };
+    Some(duration.saturating_sub(position))
+}
+
+fn round(d: Duration) -> Duration {
+    Duration::from_secs(d.as_secs())
+}
+
+/// Called whenever a track ends, falls asleep if the timer waited for it.
+/// Returns whether it did.
+pub async fn on_track_end(ctx: &Context, guild_id: GuildId, queue_empty: bool) -> bool {
+    let due = {
+        let data = ctx.data.read().await;
+        let timers = data
+            .get::<SleepTimers>()
+            .expect("SleepTimers placed in at initialisation.")
+            .lock()
+            .await;
+
+        match timers.get(&guild_id).map(|t| &t.after) {
+            Some(After::EndOfTrack) => true,
+            Some(After::EndOfQueue) => queue_empty,
+            _ => false,
+        }
+    };
+
+    if due {
+        let ctx = ctx.clone();
+        tokio::spawn(async move { fall_asleep(&ctx, guild_id).await });
+    }
+
+    due
+}
+
+async fn fall_asleep(ctx: &Context, guild_id: GuildId) {
+    {
+        let data = ctx.data.read().await;
+        data.get::<SleepTimers>()
+            .expect("SleepTimers placed in at initialisation.")
+            .lock()
+            .await
+            .remove(&guild_id);
+    }
+
+    let lang = i18n::guild_lang(ctx, guild_id).await;
+    leave::disconnect_saying(ctx, guild_id, tr(lang, "sleep.up", &[])).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_durations_and_track_ends() {
+        assert!(matches!(parse_after("1h 30m"), Some(After::Duration(_))));
+        assert!(matches!(
+            parse_after(" end-of-track "),
+            Some(After::EndOfTrack)
+        ));
+        assert!(matches!(
+            parse_after("end-of-queue"),
+            Some(After::EndOfQueue)
+        ));
+        assert!(parse_after("whenever").is_none());
+    }
+
+    #[test]
+    fn rejects_durations_past_what_the_clock_holds() {
+        assert!(parse_after("300000000000y").is_none());
+    }
+}
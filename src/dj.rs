@@ -0,0 +1,75 @@
+use crate::cmd::{check_msg, interaction_reply, manages_guild, Invocation};
+use crate::i18n::{self, tr};
+use crate::settings::{self, Privileged};
+use serenity::client::Context;
+
+/// Commands DJs get to run as they please.
+pub const PRIVILEGED: [&str; 6] = ["stop", "leave", "remove", "jump", "clear", "shuffle"];
+
+/// Commands changing guild settings, only members who can manage the guild
+/// get to run them, DJ or not.
+pub const MANAGERS_ONLY: [&str; 2] = ["config", "autoplay"];
+
+/// How a privileged command goes for whoever runs it.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Access {
+    /// Anybody may, the guild has no DJ role set up. Commands that vote still
+    /// do, nobody gets their way right away.
+    Open,
+    /// A DJ, who gets their way right away.
+    Dj,
+    /// Somebody else, who needs the others to agree.
+    Vote,
+}
+
+/// Whether whoever is behind `cmd` holds one of the guild's DJ roles.
+pub async fn is_dj(ctx: &Context, cmd: &Invocation<'_>) -> bool {
+    let (guild_id, member) = match (cmd.guild_id(), cmd.member()) {
+        (Some(guild_id), Some(member)) => (guild_id, member),
+        _ => return false,
+    };
+
+    let roles = settings::get(ctx, guild_id).await.dj_roles;
+    member.roles.iter().any(|r| roles.contains(&r.0))
+}
+
+/// Decides how `command` goes for whoever is behind `cmd`, replying and
+/// returning nothing when they are not allowed to run it at all.
+pub async fn check(ctx: &Context, cmd: &Invocation<'_>, command: &str) -> Option<Access> {
+    if MANAGERS_ONLY.contains(&command) && !manages_guild(cmd.member()) {
+        let lang = i18n::lang(ctx, cmd.guild_id(), cmd.locale()).await;
+        check_msg(
+            cmd.create_interaction_response(&ctx.http, |response| {
+                interaction_reply(response, tr(lang, "managers.only", &[&command]), true)
+            })
+            .await,
+        );
+        return None;
+    }
+
+    if !PRIVILEGED.contains(&command) {
+        return Some(Access::Open);
+    }
+
+    let settings = settings::get(ctx, cmd.guild_id()?).await;
+    if settings.dj_roles.is_empty() {
+        return Some(Access::Open);
+    }
+    if is_dj(ctx, cmd).await {
+        return Some(Access::Dj);
+    }
+
+    match settings.privileged {
+        Privileged::Vote => Some(Access::Vote),
+        Privileged::Refuse => {
+            let lang = i18n::lang(ctx, cmd.guild_id(), cmd.locale()).await;
+            check_msg(
+                cmd.create_interaction_response(&ctx.http, |response| {
+                    interaction_reply(response, tr(lang, "dj.only", &[&command]), true)
+                })
+                .await,
+            );
+            None
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use serenity::{client::Context, model::id::GuildId, prelude::TypeMapKey};
+use songbird::input::Metadata;
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Arc,
+};
+use tokio::sync::RwLock;
+
+const HISTORY_SIZE: usize = 50;
+
+#[derive(Clone)]
+pub struct Played {
+    pub url: String,
+    pub title: Option<String>,
+}
+
+/// Recently played tracks, most recent first.
+pub struct History;
+
+impl TypeMapKey for History {
+    type Value = Arc<RwLock<HashMap<GuildId, VecDeque<Played>>>>;
+}
+
+pub async fn record(ctx: &Context, guild_id: GuildId, metadata: &Metadata) {
+    let url = match &metadata.source_url {
+        Some(url) => url.clone(),
+        None => return,
+    };
+
+    let data = ctx.data.read().await;
+    let mut history = data
+        .get::<History>()
+        .expect("History placed in at initialisation.")
+        .write()
+        .await;
+
+    let played = history.entry(guild_id).or_default();
+    played.retain(|p| p.url != url);
+    played.push_front(Played {
+        url,
+        title: metadata.title.clone(),
+    });
+    played.truncate(HISTORY_SIZE);
+}
+
+pub async fn recent(ctx: &Context, guild_id: GuildId) -> Vec<Played> {
+    let data = ctx.data.read().await;
+    let history = data
+        .get::<History>()
+        .expect("History placed in at initialisation.")
+        .read()
+        .await;
+
+    history
+        .get(&guild_id)
+        .map(|played| played.iter().cloned().collect())
+        .unwrap_or_default()
+}
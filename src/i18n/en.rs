@@ -0,0 +1,181 @@
+pub const MESSAGES: &[(&str, &str)] = &[
+    ("lang.name", "English"),
+    ("yes", "yes"),
+    ("no", "no"),
+    ("none", "none"),
+    ("off", "off"),
+    ("not_implemented", "not implemented :("),
+    ("not_playing", "Not playing in a voice channel."),
+    ("not_in_voice", "Not in a voice channel"),
+    ("nowhere_to_play", "Not in a voice channel to play in"),
+    ("nothing_playing", "Nothing is playing."),
+    ("no_title", "This shit has no title?"),
+    ("invalid_url", "Must provide a valid URL"),
+    ("not_voice_channel", "Must be a voice or stage channel"),
+    ("ffmpeg_error", "Error sourcing ffmpeg"),
+    ("join_error", "Error joining the channel"),
+    ("no_track_at", "No upcoming track at position {}"),
+    // Now playing messages
+    ("np.title", "Now playing"),
+    ("np.track", "Title"),
+    ("np.url", "URL"),
+    ("np.duration", "Duration"),
+    ("np.progress", "Progress"),
+    ("np.autoplay", "📻 autoplay"),
+    ("np.live", "Live"),
+    ("np.play_pause", "⏯ Pause/Resume"),
+    ("np.skip", "⏭ Skip"),
+    ("np.stop", "⏹ Stop"),
+    ("np.loop", "🔂 Loop"),
+    ("np.shuffle", "🔀 Shuffle"),
+    ("np.queue_empty", "🕳 Queue is empty! That's sad... I guess..."),
+    ("np.next_up", "Next up: {}"),
+    ("np.next_up_by", "Next up: {}, requested by {}"),
+    // Leaving on the bot's own
+    ("alone.left", "👋 Everybody left, so did I."),
+    ("idle.left", "😴 Nothing played for {} minutes, I'm out."),
+    (
+        "reconnect.failed",
+        "📡 Lost the voice connection and could not get it back, `/join` me again",
+    ),
+    // Commands
+    ("ratelimit.wait", "⏳ Slow down, you can `/{}` again in {}s"),
+    ("dj.only", "🎧 Only DJs can `/{}`"),
+    ("managers.only", "🔒 Only members who can manage the server can `/{}`"),
+    ("join.no_channel", "Must provide a channel"),
+    (
+        "join.caller_not_in_voice",
+        "You are not in a voice channel, tell me which one to join",
+    ),
+    ("join.joined", "Joined {}"),
+    ("leave.left", "Left voice channel"),
+    ("queue.no_url", "Must provide a URL to a video or audio"),
+    ("queue.queued", "Queued **{}** at position {}"),
+    ("list.header", "**{} track(s) in queue**\n{} first tracks:"),
+    ("pause.paused", "⏸ {} paused current track"),
+    ("pause.resumed", "▶️ {} resumed current track"),
+    ("loop.on", "🔂 {} is looping current track"),
+    ("loop.off", "➡️ {} stopped looping current track"),
+    ("skip.nothing", "Nothing to skip."),
+    ("autoplay.no_value", "Must say whether autoplay is enabled"),
+    ("autoplay.enabled", "📻 {} enabled autoplay"),
+    ("autoplay.disabled", "📻 {} disabled autoplay"),
+    ("sfx.unknown", "No clip named `{}`"),
+    ("sfx.played", "🔊 {} played `{}`"),
+    ("jingle.set", "🎺 Your jingle is set, the first {}s will play"),
+    ("jingle.failed", "Could not make a jingle out of that"),
+    ("jingle.not_attachment", "Must provide the link to an audio file uploaded to Discord"),
+    ("jingle.cleared", "🎺 Your jingle is gone"),
+    ("jingle.none", "You have no jingle"),
+    ("follow.no_user", "Must provide a user to follow"),
+    ("follow.not_in_voice", "{} is not in a voice channel"),
+    ("follow.following", "👣 {} made me follow {} around"),
+    ("follow.stopped", "👣 {} made me stop following {}"),
+    ("follow.nobody", "Not following anyone"),
+    ("follow.left_voice", "👣 {} left voice, I stopped following"),
+    ("sleep.invalid", "Must provide a duration (e.g. `1h 30m`), `end-of-track` or `end-of-queue`"),
+    ("sleep.set", "💤 {} set a sleep timer {}"),
+    ("sleep.for", "for {}"),
+    ("sleep.for_track", "for the end of the current track"),
+    ("sleep.for_queue", "for the end of the queue"),
+    ("sleep.cancelled", "⏰ {} cancelled the sleep timer"),
+    ("sleep.nothing_to_cancel", "No sleep timer to cancel"),
+    ("sleep.none", "No sleep timer set"),
+    ("sleep.live", "unknown, something is live"),
+    ("sleep.in", "💤 Sleeping in {}"),
+    ("sleep.after_track", "💤 Sleeping after the current track ({} left)"),
+    ("sleep.after_queue", "💤 Sleeping after the queue ({} left)"),
+    ("sleep.up", "💤 Sleep timer is up, stopped playback and left. Good night!"),
+    ("schedule.invalid_time", "Must provide a time like 20:00"),
+    ("schedule.every_day", "Every day"),
+    ("schedule.every.0", "Mondays"),
+    ("schedule.every.1", "Tuesdays"),
+    ("schedule.every.2", "Wednesdays"),
+    ("schedule.every.3", "Thursdays"),
+    ("schedule.every.4", "Fridays"),
+    ("schedule.every.5", "Saturdays"),
+    ("schedule.every.6", "Sundays"),
+    ("schedule.entry", "`#{}` {} at {} UTC in {}: {}"),
+    ("schedule.added", "📅 {} scheduled {}"),
+    ("schedule.cancelled", "📅 {} cancelled schedule `#{}`"),
+    ("schedule.unknown", "No schedule `#{}` in this server"),
+    ("schedule.not_yours", "Only whoever added schedule `#{}` or server managers can cancel it"),
+    ("schedule.none", "Nothing scheduled"),
+    ("schedule.list", "**{} schedule(s)**\n{}"),
+    ("schedule.join_failed", "📅 Schedule `#{}` failed to join {}"),
+    ("schedule.queued", "📅 Schedule `#{}` queued {} track(s) in {}"),
+    // Votes
+    ("vote.skip", "skip **{}**"),
+    ("vote.stop", "stop playback and purge the queue"),
+    ("vote.remove", "remove **{}** from the queue"),
+    ("vote.clear", "clear upcoming tracks"),
+    ("vote.jump", "jump to **{}**"),
+    ("vote.shuffle", "shuffle the queue"),
+    ("vote.leave", "make me leave"),
+    ("vote.left", "👋 Left voice channel"),
+    ("vote.skipped", "⏭ Skipped **{}**"),
+    ("vote.not_playing", "**{}** is not playing anymore"),
+    ("vote.stopped", "⏹ Stopped and cleared queue"),
+    ("vote.removed", "⏏ Removed **{}** from the queue"),
+    ("vote.not_queued", "**{}** is not in the queue anymore"),
+    ("vote.cleared", "🧹 Cleared {} upcoming track(s)"),
+    ("vote.jumped", "⤵ Jumped to **{}**"),
+    ("vote.shuffled", "🔀 Shuffled the queue"),
+    ("vote.too_few", "Not enough tracks in queue to shuffle."),
+    ("vote.yep", "Yep"),
+    ("vote.nope", "Nope"),
+    ("vote.title", "{} wants to {}, who's with them?"),
+    ("vote.description", "You have {} seconds to vote, it takes {}."),
+    ("vote.ended", "Vote has ended."),
+    ("vote.joined", "🗳 Already voting on that, your yep went in: {}"),
+    ("vote.busy", "🗳 Another vote is running, wait for it to end: {}"),
+    ("vote.cooldown", "⏳ Your last vote failed, wait {}s before starting another"),
+    ("vote.passed", "Vote to {} succeeded\n{}"),
+    ("vote.failed", "Vote to {} failed."),
+    ("vote.already_voted", "You already voted"),
+    ("vote.by_dj", "by DJ"),
+    ("vote.by_requester", "skipped by requester"),
+    ("rule.majority", "majority of voters"),
+    ("rule.percent", "{}% of listeners"),
+    ("rule.count", "{} yep(s)"),
+    ("privileged.vote", "others vote"),
+    ("privileged.refuse", "others are refused"),
+    ("announce.invoked", "where `/join` was run"),
+    ("announce.channel", "in {}"),
+    ("announce.voice", "in the voice channel's chat"),
+    ("announce.off", "nowhere"),
+    ("nowplaying.new", "a new message for each track"),
+    ("nowplaying.edit", "one message edited for each track"),
+    ("nowplaying.replace", "a new message for each track, deleting the last one"),
+    // Settings
+    ("config.language", "Replying in {}"),
+    ("config.language_auto", "Replying in everyone's own language"),
+    ("config.language_auto_name", "everyone's own"),
+    ("config.announcements", "Announcing {}"),
+    ("config.announcements_channel", "Must provide a text or voice channel"),
+    ("config.now_playing", "Now playing: {}"),
+    ("config.alone_timeout", "Leaving {} after everybody left"),
+    ("config.idle_off", "Staying in voice channels even when idle"),
+    ("config.idle_timeout", "Leaving after {} idle minute(s)"),
+    ("config.minutes", "{} minute(s)"),
+    ("config.spoken_on", "Announcing upcoming tracks out loud"),
+    ("config.spoken_off", "No longer announcing upcoming tracks out loud"),
+    ("config.jingles_on", "Playing jingles when members join"),
+    ("config.jingles_off", "No longer playing jingles"),
+    ("config.jingle_length", "Jingles play for {}s at most"),
+    ("config.jingle_volume", "Jingles play at {}% volume"),
+    ("config.vote_rule", "Votes now take {}"),
+    ("config.vote_rule_value", "Must provide a value for this rule"),
+    ("config.vote_duration", "Votes stay open for {}s"),
+    ("config.dj_added", "{} members are now DJs"),
+    ("config.dj_removed", "{} members are no longer DJs"),
+    ("config.privileged", "DJs run privileged commands right away, {}"),
+    ("config.rate_limit", "`/{}` can be run {} time(s) every {}s"),
+    ("config.rate_limit_lifted", "No limit on `/{}` anymore"),
+    ("config.rate_limit_unknown", "There is no `/{}` command"),
+    ("config.rate_limit_entry", "`/{}` {} per {}s"),
+    (
+        "config.show",
+        "**Settings**\nLanguage: {}\nAutoplay: {}\nAnnouncements: {}\nNow playing: {}\nAlone timeout: {}\nIdle timeout: {}\nSpoken announcements: {}\nJingles: {} ({}s at most, {}% volume)\nVotes: {} within {}s\nDJ roles: {} ({})\nRate limits: {}",
+    ),
+];
@@ -0,0 +1,278 @@
+pub const MESSAGES: &[(&str, &str)] = &[
+    ("lang.name", "français"),
+    ("yes", "oui"),
+    ("no", "non"),
+    ("none", "aucun"),
+    ("off", "désactivé"),
+    ("not_implemented", "pas encore implémenté :("),
+    ("not_playing", "Je ne joue dans aucun salon vocal."),
+    ("not_in_voice", "Je ne suis dans aucun salon vocal"),
+    ("nowhere_to_play", "Aucun salon vocal où jouer"),
+    ("nothing_playing", "Rien n'est en cours de lecture."),
+    ("no_title", "Ce truc n'a même pas de titre ?"),
+    ("invalid_url", "Il faut une URL valide"),
+    ("not_voice_channel", "Il faut un salon vocal ou de conférence"),
+    ("ffmpeg_error", "Erreur à l'ouverture avec ffmpeg"),
+    ("join_error", "Impossible de rejoindre le salon"),
+    ("no_track_at", "Aucun morceau à venir en position {}"),
+    // Now playing messages
+    ("np.title", "En cours de lecture"),
+    ("np.track", "Titre"),
+    ("np.url", "URL"),
+    ("np.duration", "Durée"),
+    ("np.progress", "Progression"),
+    ("np.autoplay", "📻 lecture automatique"),
+    ("np.live", "En direct"),
+    ("np.play_pause", "⏯ Pause/Reprendre"),
+    ("np.skip", "⏭ Passer"),
+    ("np.stop", "⏹ Arrêter"),
+    ("np.loop", "🔂 Boucle"),
+    ("np.shuffle", "🔀 Mélanger"),
+    ("np.queue_empty", "🕳 La file est vide ! C'est triste... je crois..."),
+    ("np.next_up", "À suivre : {}"),
+    ("np.next_up_by", "À suivre : {}, demandé par {}"),
+    // Leaving on the bot's own
+    ("alone.left", "👋 Tout le monde est parti, moi aussi."),
+    ("idle.left", "😴 Rien joué depuis {} minutes, je m'en vais."),
+    (
+        "reconnect.failed",
+        "📡 Connexion vocale perdue sans pouvoir la rétablir, refaites `/join`",
+    ),
+    // Commands
+    ("ratelimit.wait", "⏳ Doucement, vous pourrez refaire `/{}` dans {}s"),
+    ("dj.only", "🎧 Seuls les DJ peuvent faire `/{}`"),
+    ("managers.only", "🔒 Seuls les membres qui peuvent gérer le serveur peuvent faire `/{}`"),
+    ("join.no_channel", "Il faut un salon"),
+    (
+        "join.caller_not_in_voice",
+        "Vous n'êtes dans aucun salon vocal, dites-moi lequel rejoindre",
+    ),
+    ("join.joined", "{} rejoint"),
+    ("leave.left", "Salon vocal quitté"),
+    ("queue.no_url", "Il faut l'URL d'une vidéo ou d'un son"),
+    ("queue.queued", "**{}** ajouté en position {}"),
+    ("list.header", "**{} morceau(x) dans la file**\nLes {} premiers :"),
+    ("pause.paused", "⏸ {} a mis le morceau en pause"),
+    ("pause.resumed", "▶️ {} a repris le morceau"),
+    ("loop.on", "🔂 {} fait tourner le morceau en boucle"),
+    ("loop.off", "➡️ {} a arrêté la boucle"),
+    ("skip.nothing", "Rien à passer."),
+    ("autoplay.no_value", "Il faut dire si la lecture automatique est activée"),
+    ("autoplay.enabled", "📻 {} a activé la lecture automatique"),
+    ("autoplay.disabled", "📻 {} a désactivé la lecture automatique"),
+    ("sfx.unknown", "Aucun son nommé `{}`"),
+    ("sfx.played", "🔊 {} a joué `{}`"),
+    ("jingle.set", "🎺 Votre jingle est prêt, les {} premières secondes seront jouées"),
+    ("jingle.failed", "Impossible d'en faire un jingle"),
+    ("jingle.not_attachment", "Il faut le lien d'un fichier audio envoyé sur Discord"),
+    ("jingle.cleared", "🎺 Votre jingle a disparu"),
+    ("jingle.none", "Vous n'avez pas de jingle"),
+    ("follow.no_user", "Il faut quelqu'un à suivre"),
+    ("follow.not_in_voice", "{} n'est dans aucun salon vocal"),
+    ("follow.following", "👣 {} m'a fait suivre {}"),
+    ("follow.stopped", "👣 {} m'a fait arrêter de suivre {}"),
+    ("follow.nobody", "Je ne suis personne"),
+    ("follow.left_voice", "👣 {} a quitté le vocal, j'arrête de suivre"),
+    ("sleep.invalid", "Il faut une durée (par ex. `1h 30m`), `end-of-track` ou `end-of-queue`"),
+    ("sleep.set", "💤 {} a programmé une mise en veille {}"),
+    ("sleep.for", "dans {}"),
+    ("sleep.for_track", "à la fin du morceau en cours"),
+    ("sleep.for_queue", "à la fin de la file"),
+    ("sleep.cancelled", "⏰ {} a annulé la mise en veille"),
+    ("sleep.nothing_to_cancel", "Aucune mise en veille à annuler"),
+    ("sleep.none", "Aucune mise en veille programmée"),
+    ("sleep.live", "inconnu, un direct est en cours"),
+    ("sleep.in", "💤 Mise en veille dans {}"),
+    ("sleep.after_track", "💤 Mise en veille après le morceau en cours ({} restant)"),
+    ("sleep.after_queue", "💤 Mise en veille après la file ({} restant)"),
+    ("sleep.up", "💤 C'est l'heure, lecture arrêtée et salon quitté. Bonne nuit !"),
+    ("schedule.invalid_time", "Il faut une heure comme 20:00"),
+    ("schedule.every_day", "Tous les jours"),
+    ("schedule.every.0", "Le lundi"),
+    ("schedule.every.1", "Le mardi"),
+    ("schedule.every.2", "Le mercredi"),
+    ("schedule.every.3", "Le jeudi"),
+    ("schedule.every.4", "Le vendredi"),
+    ("schedule.every.5", "Le samedi"),
+    ("schedule.every.6", "Le dimanche"),
+    ("schedule.entry", "`#{}` {} à {} UTC dans {} : {}"),
+    ("schedule.added", "📅 {} a programmé {}"),
+    ("schedule.cancelled", "📅 {} a annulé la programmation `#{}`"),
+    ("schedule.unknown", "Aucune programmation `#{}` sur ce serveur"),
+    ("schedule.not_yours", "Seuls l'auteur de la programmation `#{}` et les gestionnaires du serveur peuvent l'annuler"),
+    ("schedule.none", "Rien de programmé"),
+    ("schedule.list", "**{} programmation(s)**\n{}"),
+    ("schedule.join_failed", "📅 La programmation `#{}` n'a pas pu rejoindre {}"),
+    ("schedule.queued", "📅 La programmation `#{}` a ajouté {} morceau(x) dans {}"),
+    // Votes
+    ("vote.skip", "passer **{}**"),
+    ("vote.stop", "arrêter la lecture et vider la file"),
+    ("vote.remove", "retirer **{}** de la file"),
+    ("vote.clear", "vider les morceaux à venir"),
+    ("vote.jump", "sauter à **{}**"),
+    ("vote.shuffle", "mélanger la file"),
+    ("vote.leave", "me faire partir"),
+    ("vote.left", "👋 Salon vocal quitté"),
+    ("vote.skipped", "⏭ **{}** passé"),
+    ("vote.not_playing", "**{}** n'est plus en cours de lecture"),
+    ("vote.stopped", "⏹ Lecture arrêtée et file vidée"),
+    ("vote.removed", "⏏ **{}** retiré de la file"),
+    ("vote.not_queued", "**{}** n'est plus dans la file"),
+    ("vote.cleared", "🧹 {} morceau(x) à venir retiré(s)"),
+    ("vote.jumped", "⤵ Saut à **{}**"),
+    ("vote.shuffled", "🔀 File mélangée"),
+    ("vote.too_few", "Pas assez de morceaux dans la file pour mélanger."),
+    ("vote.yep", "Oui"),
+    ("vote.nope", "Non"),
+    ("vote.title", "{} veut {}, qui est partant ?"),
+    ("vote.description", "Vous avez {} secondes pour voter, il faut {}."),
+    ("vote.ended", "Le vote est terminé."),
+    ("vote.joined", "🗳 Ce vote est déjà en cours, votre oui est compté : {}"),
+    ("vote.busy", "🗳 Un autre vote est en cours, attendez sa fin : {}"),
+    ("vote.cooldown", "⏳ Votre dernier vote a échoué, attendez {}s avant d'en lancer un autre"),
+    ("vote.passed", "Le vote pour {} est adopté\n{}"),
+    ("vote.failed", "Le vote pour {} a échoué."),
+    ("vote.already_voted", "Vous avez déjà voté"),
+    ("vote.by_dj", "par le DJ"),
+    ("vote.by_requester", "passé par qui l'a demandé"),
+    ("rule.majority", "la majorité des votants"),
+    ("rule.percent", "{}% des auditeurs"),
+    ("rule.count", "{} oui"),
+    ("privileged.vote", "les autres votent"),
+    ("privileged.refuse", "les autres sont refusés"),
+    ("announce.invoked", "là où `/join` a été lancé"),
+    ("announce.channel", "dans {}"),
+    ("announce.voice", "dans le chat du salon vocal"),
+    ("announce.off", "nulle part"),
+    ("nowplaying.new", "un nouveau message par morceau"),
+    ("nowplaying.edit", "un seul message modifié à chaque morceau"),
+    ("nowplaying.replace", "un nouveau message par morceau, en supprimant le précédent"),
+    // Settings
+    ("config.language", "Je réponds en {}"),
+    ("config.language_auto", "Je réponds dans la langue de chacun"),
+    ("config.language_auto_name", "celle de chacun"),
+    ("config.announcements", "Annonces {}"),
+    ("config.announcements_channel", "Il faut un salon textuel ou vocal"),
+    ("config.now_playing", "En cours de lecture : {}"),
+    ("config.alone_timeout", "Je pars {} après que tout le monde est parti"),
+    ("config.idle_off", "Je reste dans les salons vocaux même sans rien jouer"),
+    ("config.idle_timeout", "Je pars après {} minute(s) sans rien jouer"),
+    ("config.minutes", "{} minute(s)"),
+    ("config.spoken_on", "J'annonce les morceaux à voix haute"),
+    ("config.spoken_off", "Je n'annonce plus les morceaux à voix haute"),
+    ("config.jingles_on", "Je joue les jingles quand quelqu'un arrive"),
+    ("config.jingles_off", "Je ne joue plus les jingles"),
+    ("config.jingle_length", "Les jingles durent {}s au plus"),
+    ("config.jingle_volume", "Les jingles sont joués à {}% du volume"),
+    ("config.vote_rule", "Les votes demandent désormais {}"),
+    ("config.vote_rule_value", "Il faut une valeur pour cette règle"),
+    ("config.vote_duration", "Les votes restent ouverts {}s"),
+    ("config.dj_added", "Les membres {} sont maintenant DJ"),
+    ("config.dj_removed", "Les membres {} ne sont plus DJ"),
+    ("config.privileged", "Les DJ lancent les commandes réservées directement, {}"),
+    ("config.rate_limit", "`/{}` peut être lancé {} fois toutes les {}s"),
+    ("config.rate_limit_lifted", "Plus de limite sur `/{}`"),
+    ("config.rate_limit_unknown", "La commande `/{}` n'existe pas"),
+    ("config.rate_limit_entry", "`/{}` {} par {}s"),
+    (
+        "config.show",
+        "**Réglages**\nLangue : {}\nLecture automatique : {}\nAnnonces : {}\nEn cours de lecture : {}\nDélai seul : {}\nDélai d'inactivité : {}\nAnnonces vocales : {}\nJingles : {} ({}s au plus, {}% du volume)\nVotes : {} en {}s\nRôles DJ : {} ({})\nLimites : {}",
+    ),
+    // Commands, as registered with Discord
+    ("command.join.description", "Faire rejoindre un salon vocal au bot"),
+    ("command.join.channel.description", "Le salon vocal ou de conférence à rejoindre, le vôtre par défaut"),
+    ("command.leave.description", "Faire quitter son salon vocal au bot"),
+    ("command.queue.description", "Ajouter un morceau à la file"),
+    ("command.queue.url.description", "L'URL YouTube à ajouter"),
+    ("command.pause.description", "Mettre le morceau en pause"),
+    ("command.resume.description", "Reprendre le morceau"),
+    ("command.skip.description", "Passer le morceau en cours"),
+    ("command.stop.description", "Arrêter et vider la file"),
+    ("command.clear.description", "Vider les morceaux à venir, garder celui en cours"),
+    ("command.list.description", "Afficher le contenu de la file"),
+    ("command.remove.description", "Retirer un morceau de la file"),
+    ("command.remove.position.description", "La position du morceau dans la file"),
+    ("command.jump.description", "Sauter directement à un morceau de la file"),
+    ("command.jump.position.description", "La position du morceau dans la file"),
+    ("command.loop.description", "Faire tourner le morceau en boucle ou non"),
+    ("command.shuffle.description", "Mélanger les morceaux à venir"),
+    ("command.sfx.description", "Jouer un son par-dessus la musique"),
+    ("command.sfx.name.description", "Le son à jouer"),
+    ("command.jingle.description", "Votre intro, jouée quand vous rejoignez le bot"),
+    ("command.jingle.set.description", "Choisir votre jingle"),
+    ("command.jingle.set.url.description", "Lien vers un fichier audio envoyé sur Discord"),
+    ("command.jingle.clear.description", "Supprimer votre jingle"),
+    ("command.autoplay.description", "Ajouter des morceaux proches quand la file se vide"),
+    ("command.autoplay.enabled.description", "Activer la lecture automatique ou non"),
+    ("command.sleep.description", "Arrêter la lecture et partir au bout d'un moment"),
+    ("command.sleep.set.description", "Programmer la mise en veille"),
+    ("command.sleep.set.after.description", "Une durée (par ex. 1h 30m), end-of-track ou end-of-queue"),
+    ("command.sleep.cancel.description", "Annuler la mise en veille"),
+    ("command.sleep.status.description", "Temps restant avant la mise en veille"),
+    ("command.follow.description", "Suivre quelqu'un de salon vocal en salon vocal"),
+    ("command.follow.user.description", "La personne à suivre"),
+    ("command.unfollow.description", "Arrêter de suivre quelqu'un"),
+    ("command.config.description", "Configurer le bot pour ce serveur"),
+    ("command.config.show.description", "Afficher les réglages"),
+    ("command.config.language.description", "La langue des réponses du bot"),
+    ("command.config.language.language.description", "Une langue, ou celle de chacun"),
+    ("command.config.language.language.auto.name", "Celle de chacun"),
+    ("command.config.announcements.description", "Où sont postés les messages de lecture et autres annonces"),
+    ("command.config.announcements.mode.description", "Où vont les annonces"),
+    ("command.config.announcements.mode.invoked.name", "Là où /join a été lancé"),
+    ("command.config.announcements.mode.channel.name", "Un salon donné"),
+    ("command.config.announcements.mode.voice.name", "Le chat du salon vocal"),
+    ("command.config.announcements.mode.off.name", "Nulle part"),
+    ("command.config.announcements.channel.description", "Le salon textuel ou vocal, pour un salon donné"),
+    ("command.config.now_playing.description", "Ce que deviennent les messages de lecture au morceau suivant"),
+    ("command.config.now_playing.mode.description", "Que faire du dernier message de lecture"),
+    ("command.config.now_playing.mode.new.name", "Le garder et en poster un nouveau"),
+    ("command.config.now_playing.mode.edit.name", "Le modifier"),
+    ("command.config.now_playing.mode.replace.name", "Le supprimer et en poster un nouveau"),
+    ("command.config.alone_timeout.description", "Délai avant de partir une fois tout le monde parti"),
+    ("command.config.alone_timeout.seconds.description", "Délai de grâce en secondes"),
+    ("command.config.spoken_announcements.description", "Annoncer à voix haute le morceau suivant"),
+    ("command.config.spoken_announcements.enabled.description", "Activer les annonces vocales ou non"),
+    ("command.config.vote_rule.description", "Ce qu'il faut pour qu'un vote passe"),
+    ("command.config.vote_rule.rule.description", "Comment les votes sont comptés"),
+    ("command.config.vote_rule.rule.majority.name", "Majorité des votants"),
+    ("command.config.vote_rule.rule.percent.name", "Pourcentage des auditeurs"),
+    ("command.config.vote_rule.rule.count.name", "Nombre de oui"),
+    ("command.config.vote_rule.value.description", "Le pourcentage ou nombre de oui nécessaire"),
+    ("command.config.vote_duration.description", "Durée d'ouverture des votes"),
+    ("command.config.vote_duration.seconds.description", "Durée du vote en secondes"),
+    ("command.config.dj_role.description", "Ajouter ou retirer un rôle dont les membres passent et modifient la file sans vote"),
+    ("command.config.dj_role.role.description", "Le rôle à ajouter ou retirer"),
+    ("command.config.privileged.description", "Ce qui se passe quand d'autres que les DJ arrêtent, font partir ou modifient la file"),
+    ("command.config.privileged.mode.description", "Voter ou refuser"),
+    ("command.config.privileged.mode.vote.name", "Voter"),
+    ("command.config.privileged.mode.refuse.name", "Refuser"),
+    ("command.config.rate_limit.description", "Limiter la fréquence d'une commande pour chaque membre"),
+    ("command.config.rate_limit.command.description", "La commande à limiter, par ex. queue"),
+    ("command.config.rate_limit.uses.description", "Nombre d'utilisations permises, 0 pour lever la limite"),
+    ("command.config.rate_limit.seconds.description", "Sur combien de secondes, 60 par défaut"),
+    ("command.config.jingles.description", "Jouer les jingles des membres quand ils arrivent"),
+    ("command.config.jingles.enabled.description", "Activer les jingles ou non"),
+    ("command.config.jingle_length.description", "Durée maximale d'un jingle"),
+    ("command.config.jingle_length.seconds.description", "Durée maximale en secondes"),
+    ("command.config.jingle_volume.description", "Volume des jingles"),
+    ("command.config.jingle_volume.percent.description", "Volume en pourcentage"),
+    ("command.config.idle_timeout.description", "Délai avant de partir quand rien ne joue"),
+    ("command.config.idle_timeout.minutes.description", "Minutes d'inactivité, 0 pour ne jamais partir"),
+    ("command.schedule.description", "Prévoir une lecture à l'avance"),
+    ("command.schedule.add.description", "Rejoindre un salon vocal et lancer une playlist à heure fixe"),
+    ("command.schedule.add.channel.description", "Le salon vocal ou de conférence à rejoindre"),
+    ("command.schedule.add.time.description", "Heure de la journée, UTC (par ex. 20:00)"),
+    ("command.schedule.add.url.description", "L'URL du morceau ou de la playlist à ajouter"),
+    ("command.schedule.add.day.description", "Jour de la semaine, tous les jours si absent"),
+    ("command.schedule.add.day.Monday.name", "Lundi"),
+    ("command.schedule.add.day.Tuesday.name", "Mardi"),
+    ("command.schedule.add.day.Wednesday.name", "Mercredi"),
+    ("command.schedule.add.day.Thursday.name", "Jeudi"),
+    ("command.schedule.add.day.Friday.name", "Vendredi"),
+    ("command.schedule.add.day.Saturday.name", "Samedi"),
+    ("command.schedule.add.day.Sunday.name", "Dimanche"),
+    ("command.schedule.list.description", "Lister les lectures programmées"),
+    ("command.schedule.cancel.description", "Annuler une lecture programmée"),
+    ("command.schedule.cancel.id.description", "Le numéro de la programmation, voir /schedule list"),
+];
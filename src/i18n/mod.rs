@@ -0,0 +1,296 @@
+use crate::settings;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use serenity::{client::Context, model::id::GuildId};
+use std::fmt::{Display, Write};
+
+mod en;
+mod fr;
+
+/// Languages the bot speaks, each with its own message catalog.
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Lang {
+    En,
+    Fr,
+}
+
+impl Lang {
+    pub const ALL: [Lang; 2] = [Lang::En, Lang::Fr];
+
+    /// Picks the language matching a Discord locale such as `en-US` or `fr`.
+    pub fn from_locale(locale: &str) -> Option<Self> {
+        match locale.split('-').next() {
+            Some("en") => Some(Self::En),
+            Some("fr") => Some(Self::Fr),
+            _ => None,
+        }
+    }
+
+    /// Two letter code, as offered by `/config language` and understood by
+    /// espeak.
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::En => "en",
+            Self::Fr => "fr",
+        }
+    }
+
+    /// Discord locale used for command localizations.
+    fn locale(self) -> &'static str {
+        match self {
+            Self::En => "en-US",
+            Self::Fr => "fr",
+        }
+    }
+
+    fn messages(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Self::En => en::MESSAGES,
+            Self::Fr => fr::MESSAGES,
+        }
+    }
+}
+
+/// Language to reply in: the guild's if it chose one, the user's otherwise.
+pub async fn lang(ctx: &Context, guild_id: Option<GuildId>, locale: &str) -> Lang {
+    if let Some(guild_id) = guild_id {
+        if let Some(lang) = settings::get(ctx, guild_id).await.language {
+            return lang;
+        }
+    }
+
+    Lang::from_locale(locale).unwrap_or(Lang::En)
+}
+
+/// Language for messages nobody asked for, such as announcements: the one
+/// the guild chose, or its community locale.
+pub async fn guild_lang(ctx: &Context, guild_id: GuildId) -> Lang {
+    if let Some(lang) = settings::get(ctx, guild_id).await.language {
+        return lang;
+    }
+
+    ctx.cache
+        .guild_field(guild_id, |g| Lang::from_locale(&g.preferred_locale))
+        .await
+        .flatten()
+        .unwrap_or(Lang::En)
+}
+
+fn lookup(lang: Lang, key: &str) -> Option<&'static str> {
+    find(lang.messages(), key)
+}
+
+fn find<'a>(messages: &[(&str, &'a str)], key: &str) -> Option<&'a str> {
+    messages
+        .iter()
+        .find(|(k, _)| *k == key)
+        .map(|(_, message)| *message)
+}
+
+/// The message `key` in `messages`, in `fallback` when missing, the key itself
+/// when missing there too.
+fn message<'a>(
+    messages: &[(&str, &'a str)],
+    fallback: &[(&str, &'a str)],
+    key: &'a str,
+) -> &'a str {
+    find(messages, key)
+        .or_else(|| find(fallback, key))
+        .unwrap_or(key)
+}
+
+/// The message `key` in `lang`, falling back to English. Each `{}` in the
+/// message takes the next of `args`, `{0}`, `{1}`... pick one by position.
+pub fn tr(lang: Lang, key: &str, args: &[&(dyn Display + Sync)]) -> String {
+    let message = message(lang.messages(), Lang::En.messages(), key);
+
+    let mut filled = String::with_capacity(message.len());
+    let mut next = 0;
+    let mut rest = message;
+    while let Some(start) = rest.find('{') {
+        let end = match rest[start..].find('}') {
+            Some(end) => start + end,
+            None => break,
+        };
+        let index = match &rest[start + 1..end] {
+            "" => {
+                next += 1;
+                Some(next - 1)
+            }
+            n => n.parse::<usize>().ok(),
+        };
+
+        filled.push_str(&rest[..start]);
+        match index.and_then(|i| args.get(i)) {
+            Some(arg) => {
+                let _ = write!(filled, "{}", arg);
+            }
+            None => filled.push_str(&rest[start..=end]),
+        }
+        rest = &rest[end + 1..];
+    }
+    filled.push_str(rest);
+
+    filled
+}
+
+/// Adds the catalogs' translations of descriptions and choice names to
+/// commands about to be registered. Keys look like
+/// `command.config.vote_rule.description`, choices going by their value.
+/// Command and option names stay as they are, for messages and
+/// `/config rate_limit` to refer to them the way everyone sees them.
+pub fn localize_commands(commands: &mut [Value]) {
+    for command in commands.iter_mut() {
+        localize(command, "command");
+    }
+}
+
+fn localize(item: &mut Value, parent: &str) {
+    let item = match item.as_object_mut() {
+        Some(item) => item,
+        None => return,
+    };
+
+    let segment = match item.get("value").or_else(|| item.get("name")) {
+        Some(Value::String(segment)) => segment.clone(),
+        _ => return,
+    };
+    let key = format!("{}.{}", parent, segment);
+
+    let choice = item.contains_key("value");
+    for field in ["name", "description"] {
+        if !item.contains_key(field) || (field == "name" && !choice) {
+            continue;
+        }
+
+        let translations = Lang::ALL
+            .iter()
+            .filter(|lang| **lang != Lang::En)
+            .filter_map(|lang| {
+                let message = lookup(*lang, &format!("{}.{}", key, field))?;
+                Some((lang.locale().to_string(), Value::from(message)))
+            })
+            .collect::<Map<String, Value>>();
+
+        if !translations.is_empty() {
+            item.insert(
+                format!("{}_localizations", field),
+                Value::Object(translations),
+            );
+        }
+    }
+
+    for children in ["options", "choices"] {
+        if let Some(Value::Array(children)) = item.get_mut(children) {
+            for child in children.iter_mut() {
+                localize(child, &key);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn keys(messages: &[(&'static str, &'static str)]) -> HashSet<&'static str> {
+        messages.iter().map(|(key, _)| *key).collect()
+    }
+
+    #[test]
+    fn fills_placeholders_in_order_or_by_position() {
+        assert_eq!(
+            tr(Lang::En, "no_track_at", &[&3]),
+            "No upcoming track at position 3"
+        );
+        assert_eq!(
+            tr(Lang::En, "config.rate_limit", &[&"queue", &5, &60]),
+            "`/queue` can be run 5 time(s) every 60s"
+        );
+        // Unknown keys come out as they are, placeholders filled.
+        assert_eq!(tr(Lang::En, "{1} then {0}", &[&"a", &"b"]), "b then a");
+    }
+
+    #[test]
+    fn keeps_placeholders_without_args_and_ignores_extra_ones() {
+        assert_eq!(
+            tr(Lang::En, "no_track_at", &[]),
+            "No upcoming track at position {}"
+        );
+        assert_eq!(tr(Lang::En, "yes", &[&1, &2]), "yes");
+    }
+
+    #[test]
+    fn falls_back_to_english_then_to_the_key() {
+        let fr = [("yes", "oui")];
+        let en = [("yes", "yes"), ("no", "no")];
+
+        assert_eq!(message(&fr, &en, "yes"), "oui");
+        assert_eq!(message(&fr, &en, "no"), "no");
+        assert_eq!(message(&fr, &en, "missing.key"), "missing.key");
+        assert_eq!(tr(Lang::Fr, "yes", &[]), "oui");
+        assert_eq!(tr(Lang::Fr, "missing.key", &[]), "missing.key");
+    }
+
+    #[test]
+    fn catalogs_have_the_same_messages() {
+        let en = keys(en::MESSAGES);
+        let fr = keys(fr::MESSAGES)
+            .into_iter()
+            // Command localizations only exist for other languages.
+            .filter(|key| !key.starts_with("command."))
+            .collect::<HashSet<_>>();
+
+        assert_eq!(en.len(), en::MESSAGES.len(), "duplicate keys in en");
+        assert_eq!(
+            en.difference(&fr).collect::<Vec<_>>(),
+            Vec::<&&str>::new(),
+            "missing from fr"
+        );
+        assert_eq!(
+            fr.difference(&en).collect::<Vec<_>>(),
+            Vec::<&&str>::new(),
+            "missing from en"
+        );
+    }
+
+    #[test]
+    fn localizes_descriptions_and_choices_but_not_names() {
+        let mut commands = [serde_json::json!({
+            "name": "config",
+            "description": "Configure the bot for this server",
+            "options": [{
+                "name": "privileged",
+                "description": "What happens when someone who is not a DJ runs a privileged command",
+                "options": [{
+                    "name": "mode",
+                    "description": "Vote or refuse",
+                    "choices": [{ "name": "Vote", "value": "vote" }],
+                }],
+            }],
+        })];
+        localize_commands(&mut commands);
+
+        let command = &commands[0];
+        assert!(command.get("name_localizations").is_none());
+        assert_eq!(
+            command["description_localizations"]["fr"],
+            tr(Lang::Fr, "command.config.description", &[])
+        );
+        let option = &command["options"][0];
+        assert!(option.get("name_localizations").is_none());
+        assert_eq!(
+            option["options"][0]["choices"][0]["name_localizations"]["fr"],
+            tr(Lang::Fr, "command.config.privileged.mode.vote.name", &[])
+        );
+    }
+
+    #[test]
+    fn picks_languages_from_locales() {
+        assert!(Lang::from_locale("en-GB") == Some(Lang::En));
+        assert!(Lang::from_locale("fr") == Some(Lang::Fr));
+        assert!(Lang::from_locale("de").is_none());
+    }
+}
@@ -1,38 +1,90 @@
+mod alone;
+mod announce;
 mod cmd;
+mod dj;
+mod history;
+mod i18n;
+mod mix;
+mod progress;
+mod ratelimit;
+mod settings;
+mod stage;
+mod tts;
+mod vote;
+mod ytdl;
 
 use crate::{
+    alone::AloneTimers,
+    announce::{Announce, NowPlaying},
+    cmd::autoplay::autoplay,
+    cmd::clear::clear,
+    cmd::config::{config, MAX_JINGLE_LENGTH, MAX_VOTE_DURATION, MIN_VOTE_DURATION},
+    cmd::follow::{self, follow, unfollow, Following},
+    cmd::jingle::{self, jingle},
     cmd::join::join,
+    cmd::jump::jump,
     cmd::leave::leave,
-    cmd::list::list,
+    cmd::list::{self, list},
+    cmd::loop_track::loop_track,
     cmd::play_pause::{play_pause, Op},
-    cmd::queue::queue,
+    cmd::queue::{self, queue},
+    cmd::remove::remove,
+    cmd::schedule::{self, schedule, Schedules, DAYS},
+    cmd::sfx::{self, sfx},
+    cmd::shuffle::shuffle,
     cmd::skip::skip,
+    cmd::sleep::{sleep, SleepTimers},
     cmd::stop::stop,
-    cmd::{check_msg, interaction_reply},
+    cmd::{check_msg, interaction_reply, ControlBtn, Invocation, Registered},
+    history::History,
+    i18n::{tr, Lang},
+    mix::Mixing,
+    progress::Progress,
+    ratelimit::RateLimits,
+    settings::{DataDir, Settings},
+    vote::Votes,
 };
+use serde_json::Value;
 use serenity::{
     async_trait,
+    builder::CreateApplicationCommand,
     client::{bridge::gateway::GatewayIntents, Client, Context, EventHandler},
     model::{
         channel::ChannelType,
         gateway::Ready,
+        id::GuildId,
         interactions::{
             application_command::{ApplicationCommand, ApplicationCommandOptionType},
             Interaction,
         },
+        permissions::Permissions,
+        voice::VoiceState,
     },
 };
 use songbird::SerenityInit;
-use std::env;
+use std::{collections::HashMap, env, path::PathBuf, sync::Arc};
+use tokio::sync::{Mutex, RwLock};
 
 struct Handler;
 
+/// Only offers `command` to members who can manage the guild, the builder has
+/// no method for it.
+fn managers_only(command: &mut CreateApplicationCommand) -> &mut CreateApplicationCommand {
+    command.0.insert(
+        "default_member_permissions",
+        Value::from(Permissions::MANAGE_GUILD.bits().to_string()),
+    );
+    command
+}
+
 #[async_trait]
 impl EventHandler for Handler {
     async fn ready(&self, ctx: Context, ready: Ready) {
         println!("{} is connected!", ready.user.name);
 
         ctx.reset_presence().await;
+        schedule::start(&ctx).await;
+        progress::start(&ctx).await;
 
         let commands = ApplicationCommand::set_global_application_commands(&ctx.http, |commands| {
             commands
@@ -43,10 +95,10 @@ impl EventHandler for Handler {
                         .create_option(|option| {
                             option
                                 .name("channel")
-                                .description("The voice channel to join")
+                                .description("The voice or stage channel to join, yours by default")
                                 .kind(ApplicationCommandOptionType::Channel)
-                                .channel_types(&[ChannelType::Voice])
-                                .required(true)
+                                .channel_types(&[ChannelType::Voice, ChannelType::Stage])
+                                .required(false)
                         })
                 })
                 .create_application_command(|command| {
@@ -63,6 +115,7 @@ impl EventHandler for Handler {
                                 .name("url")
                                 .description("The YouTube url to queue")
                                 .kind(ApplicationCommandOptionType::String)
+                                .set_autocomplete(true)
                                 .required(true)
                         })
                 })
@@ -80,39 +133,552 @@ impl EventHandler for Handler {
                         .name("stop")
                         .description("Stop and purge tracks queue")
                 })
+                .create_application_command(|command| {
+                    command
+                        .name("clear")
+                        .description("Purge upcoming tracks, keep the current one")
+                })
                 .create_application_command(|command| {
                     command.name("list").description("List queue content")
                 })
+                .create_application_command(|command| {
+                    command
+                        .name("remove")
+                        .description("Remove a track from the queue")
+                        .create_option(|option| {
+                            option
+                                .name("position")
+                                .description("The track's position in the queue")
+                                .kind(ApplicationCommandOptionType::Integer)
+                                .min_int_value(2)
+                                .set_autocomplete(true)
+                                .required(true)
+                        })
+                })
+                .create_application_command(|command| {
+                    command
+                        .name("jump")
+                        .description("Skip ahead to a track in the queue")
+                        .create_option(|option| {
+                            option
+                                .name("position")
+                                .description("The track's position in the queue")
+                                .kind(ApplicationCommandOptionType::Integer)
+                                .min_int_value(2)
+                                .set_autocomplete(true)
+                                .required(true)
+                        })
+                })
+                .create_application_command(|command| {
+                    command
+                        .name("loop")
+                        .description("Toggle looping of current track")
+                })
+                .create_application_command(|command| {
+                    command
+                        .name("shuffle")
+                        .description("Shuffle upcoming tracks")
+                })
+                .create_application_command(|command| {
+                    command
+                        .name("sfx")
+                        .description("Play a sound clip over the music")
+                        .create_option(|option| {
+                            option
+                                .name("name")
+                                .description("The clip to play")
+                                .kind(ApplicationCommandOptionType::String)
+                                .set_autocomplete(true)
+                                .required(true)
+                        })
+                })
+                .create_application_command(|command| {
+                    command
+                        .name("jingle")
+                        .description("Your own intro, played when you join the bot")
+                        .create_option(|option| {
+                            option
+                                .name("set")
+                                .description("Set your jingle")
+                                .kind(ApplicationCommandOptionType::SubCommand)
+                                .create_sub_option(|sub| {
+                                    sub.name("url")
+                                        .description("Link to an audio file uploaded to Discord")
+                                        .kind(ApplicationCommandOptionType::String)
+                                        .required(true)
+                                })
+                        })
+                        .create_option(|option| {
+                            option
+                                .name("clear")
+                                .description("Remove your jingle")
+                                .kind(ApplicationCommandOptionType::SubCommand)
+                        })
+                })
+                .create_application_command(|command| {
+                    managers_only(command)
+                        .name("autoplay")
+                        .description("Queue related tracks when the queue runs dry")
+                        .create_option(|option| {
+                            option
+                                .name("enabled")
+                                .description("Whether autoplay is enabled")
+                                .kind(ApplicationCommandOptionType::Boolean)
+                                .required(true)
+                        })
+                })
+                .create_application_command(|command| {
+                    command
+                        .name("sleep")
+                        .description("Stop playback and leave after a while")
+                        .create_option(|option| {
+                            option
+                                .name("set")
+                                .description("Set the sleep timer")
+                                .kind(ApplicationCommandOptionType::SubCommand)
+                                .create_sub_option(|sub| {
+                                    sub.name("after")
+                                        .description(
+                                            "A duration (e.g. 1h 30m), end-of-track or end-of-queue",
+                                        )
+                                        .kind(ApplicationCommandOptionType::String)
+                                        .required(true)
+                                })
+                        })
+                        .create_option(|option| {
+                            option
+                                .name("cancel")
+                                .description("Cancel the sleep timer")
+                                .kind(ApplicationCommandOptionType::SubCommand)
+                        })
+                        .create_option(|option| {
+                            option
+                                .name("status")
+                                .description("Show time left on the sleep timer")
+                                .kind(ApplicationCommandOptionType::SubCommand)
+                        })
+                })
+                .create_application_command(|command| {
+                    command
+                        .name("follow")
+                        .description("Move along with someone across voice channels")
+                        .create_option(|option| {
+                            option
+                                .name("user")
+                                .description("The user to follow")
+                                .kind(ApplicationCommandOptionType::User)
+                                .required(true)
+                        })
+                })
+                .create_application_command(|command| {
+                    command
+                        .name("unfollow")
+                        .description("Stop following someone around")
+                })
+                .create_application_command(|command| {
+                    managers_only(command)
+                        .name("config")
+                        .description("Configure the bot for this server")
+                        .create_option(|option| {
+                            option
+                                .name("show")
+                                .description("Show current settings")
+                                .kind(ApplicationCommandOptionType::SubCommand)
+                        })
+                        .create_option(|option| {
+                            option
+                                .name("announcements")
+                                .description("Where now playing messages and such are posted")
+                                .kind(ApplicationCommandOptionType::SubCommand)
+                                .create_sub_option(|sub| {
+                                    sub.name("mode")
+                                        .description("Where announcements go")
+                                        .kind(ApplicationCommandOptionType::String)
+                                        .required(true)
+                                        .add_string_choice("Where /join was run", "invoked")
+                                        .add_string_choice("A given channel", "channel")
+                                        .add_string_choice("The voice channel's chat", "voice")
+                                        .add_string_choice("Nowhere", "off")
+                                })
+                                .create_sub_option(|sub| {
+                                    sub.name("channel")
+                                        .description("The text or voice channel, for a given channel")
+                                        .kind(ApplicationCommandOptionType::Channel)
+                                        .channel_types(&[
+                                            ChannelType::Text,
+                                            ChannelType::News,
+                                            ChannelType::Voice,
+                                            ChannelType::Stage,
+                                        ])
+                                })
+                        })
+                        .create_option(|option| {
+                            option
+                                .name("now_playing")
+                                .description("What becomes of now playing messages on the next track")
+                                .kind(ApplicationCommandOptionType::SubCommand)
+                                .create_sub_option(|sub| {
+                                    sub.name("mode")
+                                        .description("What to do with the last now playing message")
+                                        .kind(ApplicationCommandOptionType::String)
+                                        .required(true)
+                                        .add_string_choice("Keep it and post a new one", "new")
+                                        .add_string_choice("Edit it in place", "edit")
+                                        .add_string_choice("Delete it and post a new one", "replace")
+                                })
+                        })
+                        .create_option(|option| {
+                            option
+                                .name("language")
+                                .description("Language the bot replies in")
+                                .kind(ApplicationCommandOptionType::SubCommand)
+                                .create_sub_option(|sub| {
+                                    sub.name("language")
+                                        .description("A language, or everyone's own")
+                                        .kind(ApplicationCommandOptionType::String)
+                                        .required(true)
+                                        .add_string_choice("Everyone's own", "auto");
+                                    for lang in Lang::ALL {
+                                        sub.add_string_choice(tr(lang, "lang.name", &[]), lang.code());
+                                    }
+                                    sub
+                                })
+                        })
+                        .create_option(|option| {
+                            option
+                                .name("alone_timeout")
+                                .description("Time to wait before leaving once everybody left")
+                                .kind(ApplicationCommandOptionType::SubCommand)
+                                .create_sub_option(|sub| {
+                                    sub.name("seconds")
+                                        .description("Grace period in seconds")
+                                        .kind(ApplicationCommandOptionType::Integer)
+                                        .min_int_value(0)
+                                        .required(true)
+                                })
+                        })
+                        .create_option(|option| {
+                            option
+                                .name("spoken_announcements")
+                                .description("Say what plays next out loud in the voice channel")
+                                .kind(ApplicationCommandOptionType::SubCommand)
+                                .create_sub_option(|sub| {
+                                    sub.name("enabled")
+                                        .description("Whether spoken announcements are enabled")
+                                        .kind(ApplicationCommandOptionType::Boolean)
+                                        .required(true)
+                                })
+                        })
+                        .create_option(|option| {
+                            option
+                                .name("vote_rule")
+                                .description("What it takes for a vote to pass")
+                                .kind(ApplicationCommandOptionType::SubCommand)
+                                .create_sub_option(|sub| {
+                                    sub.name("rule")
+                                        .description("How votes are counted")
+                                        .kind(ApplicationCommandOptionType::String)
+                                        .add_string_choice("Majority of voters", "majority")
+                                        .add_string_choice("Percentage of listeners", "percent")
+                                        .add_string_choice("Number of yeps", "count")
+                                        .required(true)
+                                })
+                                .create_sub_option(|sub| {
+                                    sub.name("value")
+                                        .description("The percentage or number of yeps needed")
+                                        .kind(ApplicationCommandOptionType::Integer)
+                                        .min_int_value(1)
+                                })
+                        })
+                        .create_option(|option| {
+                            option
+                                .name("vote_duration")
+                                .description("How long votes stay open")
+                                .kind(ApplicationCommandOptionType::SubCommand)
+                                .create_sub_option(|sub| {
+                                    sub.name("seconds")
+                                        .description("Vote length in seconds")
+                                        .kind(ApplicationCommandOptionType::Integer)
+                                        .min_int_value(MIN_VOTE_DURATION)
+                                        .max_int_value(MAX_VOTE_DURATION)
+                                        .required(true)
+                                })
+                        })
+                        .create_option(|option| {
+                            option
+                                .name("dj_role")
+                                .description("Add or remove a role whose members skip and edit the queue without a vote")
+                                .kind(ApplicationCommandOptionType::SubCommand)
+                                .create_sub_option(|sub| {
+                                    sub.name("role")
+                                        .description("The role to toggle")
+                                        .kind(ApplicationCommandOptionType::Role)
+                                        .required(true)
+                                })
+                        })
+                        .create_option(|option| {
+                            option
+                                .name("privileged")
+                                .description("What happens when others than DJs stop, leave or edit the queue")
+                                .kind(ApplicationCommandOptionType::SubCommand)
+                                .create_sub_option(|sub| {
+                                    sub.name("mode")
+                                        .description("Vote on it or refuse it")
+                                        .kind(ApplicationCommandOptionType::String)
+                                        .add_string_choice("Vote", "vote")
+                                        .add_string_choice("Refuse", "refuse")
+                                        .required(true)
+                                })
+                        })
+                        .create_option(|option| {
+                            option
+                                .name("rate_limit")
+                                .description("Limit how often each member may run a command")
+                                .kind(ApplicationCommandOptionType::SubCommand)
+                                .create_sub_option(|sub| {
+                                    sub.name("command")
+                                        .description("The command to limit, e.g. queue")
+                                        .kind(ApplicationCommandOptionType::String)
+                                        .required(true)
+                                })
+                                .create_sub_option(|sub| {
+                                    sub.name("uses")
+                                        .description("Runs allowed, 0 to lift the limit")
+                                        .kind(ApplicationCommandOptionType::Integer)
+                                        .min_int_value(0)
+                                        .required(true)
+                                })
+                                .create_sub_option(|sub| {
+                                    sub.name("seconds")
+                                        .description("Over how many seconds, 60 by default")
+                                        .kind(ApplicationCommandOptionType::Integer)
+                                        .min_int_value(1)
+                                })
+                        })
+                        .create_option(|option| {
+                            option
+                                .name("jingles")
+                                .description("Play members' jingles when they join")
+                                .kind(ApplicationCommandOptionType::SubCommand)
+                                .create_sub_option(|sub| {
+                                    sub.name("enabled")
+                                        .description("Whether jingles are enabled")
+                                        .kind(ApplicationCommandOptionType::Boolean)
+                                        .required(true)
+                                })
+                        })
+                        .create_option(|option| {
+                            option
+                                .name("jingle_length")
+                                .description("Longest a jingle may play")
+                                .kind(ApplicationCommandOptionType::SubCommand)
+                                .create_sub_option(|sub| {
+                                    sub.name("seconds")
+                                        .description("Length cap in seconds")
+                                        .kind(ApplicationCommandOptionType::Integer)
+                                        .min_int_value(1)
+                                        .max_int_value(MAX_JINGLE_LENGTH)
+                                        .required(true)
+                                })
+                        })
+                        .create_option(|option| {
+                            option
+                                .name("jingle_volume")
+                                .description("How loud jingles play")
+                                .kind(ApplicationCommandOptionType::SubCommand)
+                                .create_sub_option(|sub| {
+                                    sub.name("percent")
+                                        .description("Volume in percent")
+                                        .kind(ApplicationCommandOptionType::Integer)
+                                        .min_int_value(0)
+                                        .max_int_value(100)
+                                        .required(true)
+                                })
+                        })
+                        .create_option(|option| {
+                            option
+                                .name("idle_timeout")
+                                .description("Time to wait before leaving once nothing plays")
+                                .kind(ApplicationCommandOptionType::SubCommand)
+                                .create_sub_option(|sub| {
+                                    sub.name("minutes")
+                                        .description("Idle minutes, 0 to never leave")
+                                        .kind(ApplicationCommandOptionType::Integer)
+                                        .min_int_value(0)
+                                        .required(true)
+                                })
+                        })
+                })
+                .create_application_command(|command| {
+                    command
+                        .name("schedule")
+                        .description("Plan playback ahead of time")
+                        .create_option(|option| {
+                            option
+                                .name("add")
+                                .description("Join a voice channel and queue a playlist at a set time")
+                                .kind(ApplicationCommandOptionType::SubCommand)
+                                .create_sub_option(|sub| {
+                                    sub.name("channel")
+                                        .description("The voice or stage channel to join")
+                                        .kind(ApplicationCommandOptionType::Channel)
+                                        .channel_types(&[ChannelType::Voice, ChannelType::Stage])
+                                        .required(true)
+                                })
+                                .create_sub_option(|sub| {
+                                    sub.name("time")
+                                        .description("Time of day, UTC (e.g. 20:00)")
+                                        .kind(ApplicationCommandOptionType::String)
+                                        .required(true)
+                                })
+                                .create_sub_option(|sub| {
+                                    sub.name("url")
+                                        .description("The track or playlist url to queue")
+                                        .kind(ApplicationCommandOptionType::String)
+                                        .required(true)
+                                })
+                                .create_sub_option(|sub| {
+                                    sub.name("day")
+                                        .description("Day of the week, every day if unset")
+                                        .kind(ApplicationCommandOptionType::String);
+                                    DAYS.iter().for_each(|d| {
+                                        sub.add_string_choice(d, d);
+                                    });
+                                    sub
+                                })
+                        })
+                        .create_option(|option| {
+                            option
+                                .name("list")
+                                .description("List scheduled playbacks")
+                                .kind(ApplicationCommandOptionType::SubCommand)
+                        })
+                        .create_option(|option| {
+                            option
+                                .name("cancel")
+                                .description("Cancel a scheduled playback")
+                                .kind(ApplicationCommandOptionType::SubCommand)
+                                .create_sub_option(|sub| {
+                                    sub.name("id")
+                                        .description("The schedule number, see /schedule list")
+                                        .kind(ApplicationCommandOptionType::Integer)
+                                        .required(true)
+                                })
+                        })
+                });
+
+            i18n::localize_commands(&mut commands.0);
+            commands
         })
         .await;
 
-        commands.unwrap().iter().for_each(|c| {
+        let commands = commands.unwrap();
+        commands.iter().for_each(|c| {
             println!("Created GLOBAL [{}] /{}", c.id, c.name);
         });
+        ctx.data
+            .write()
+            .await
+            .insert::<Registered>(commands.into_iter().map(|c| c.name).collect());
     }
 
     async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
-        if let Interaction::ApplicationCommand(command) = interaction {
-            let _ = match command.data.name.as_str() {
-                "join" => join(&ctx, &command).await,
-                "leave" => leave(&ctx, &command).await,
-                "queue" => queue(&ctx, &command).await,
-                "pause" => play_pause(&ctx, &command, Op::Pause).await,
-                "resume" => play_pause(&ctx, &command, Op::Resume).await,
-                "skip" => skip(&ctx, &command).await,
-                "stop" => stop(&ctx, &command).await,
-                "list" => list(&ctx, &command).await,
-
-                _ => {
-                    return check_msg(
-                        command
-                            .create_interaction_response(&ctx.http, |response| {
-                                interaction_reply(response, "not implemented :(".to_string(), false)
-                            })
-                            .await,
-                    )
+        match interaction {
+            Interaction::ApplicationCommand(command) => {
+                let invocation = Invocation::Command(&command);
+                // Refused commands do not count towards the limit.
+                let access = match dj::check(&ctx, &invocation, &command.data.name).await {
+                    Some(access) => access,
+                    None => return,
+                };
+                if !ratelimit::check(&ctx, &invocation, &command.data.name).await {
+                    return;
                 }
-            };
+
+                let _ = match command.data.name.as_str() {
+                    "join" => join(&ctx, &command).await,
+                    "leave" => leave(&ctx, &command, access).await,
+                    "queue" => queue(&ctx, &command).await,
+                    "pause" => play_pause(&ctx, &invocation, Op::Pause).await,
+                    "resume" => play_pause(&ctx, &invocation, Op::Resume).await,
+                    "skip" => skip(&ctx, &invocation).await,
+                    "stop" => stop(&ctx, &invocation, access).await,
+                    "clear" => clear(&ctx, &invocation, access).await,
+                    "list" => list(&ctx, &command).await,
+                    "remove" => remove(&ctx, &command, access).await,
+                    "jump" => jump(&ctx, &command, access).await,
+                    "loop" => loop_track(&ctx, &invocation).await,
+                    "shuffle" => shuffle(&ctx, &invocation, access).await,
+                    "sfx" => sfx(&ctx, &command).await,
+                    "jingle" => jingle(&ctx, &command).await,
+                    "autoplay" => autoplay(&ctx, &command).await,
+                    "sleep" => sleep(&ctx, &command).await,
+                    "schedule" => schedule(&ctx, &command).await,
+                    "config" => config(&ctx, &command).await,
+                    "follow" => follow(&ctx, &command).await,
+                    "unfollow" => unfollow(&ctx, &command).await,
+
+                    _ => {
+                        let lang = i18n::lang(&ctx, command.guild_id, &command.locale).await;
+                        return check_msg(
+                            command
+                                .create_interaction_response(&ctx.http, |response| {
+                                    interaction_reply(
+                                        response,
+                                        tr(lang, "not_implemented", &[]),
+                                        false,
+                                    )
+                                })
+                                .await,
+                        );
+                    }
+                };
+            }
+            Interaction::MessageComponent(component) => {
+                let invocation = Invocation::Component(&component);
+                let btn = match ControlBtn::from_custom_id(&component.data.custom_id) {
+                    Some(btn) => btn,
+                    // Any other button belongs to a command's own collector.
+                    None => return,
+                };
+                // Refused commands do not count towards the limit.
+                let access = match dj::check(&ctx, &invocation, btn.command()).await {
+                    Some(access) => access,
+                    None => return,
+                };
+                if !ratelimit::check(&ctx, &invocation, btn.command()).await {
+                    return;
+                }
+
+                let _ = match btn {
+                    ControlBtn::PlayPause => play_pause(&ctx, &invocation, Op::Toggle).await,
+                    ControlBtn::Skip => skip(&ctx, &invocation).await,
+                    ControlBtn::Stop => stop(&ctx, &invocation, access).await,
+                    ControlBtn::Loop => loop_track(&ctx, &invocation).await,
+                    ControlBtn::Shuffle => shuffle(&ctx, &invocation, access).await,
+                };
+            }
+            Interaction::Autocomplete(autocomplete) => match autocomplete.data.name.as_str() {
+                "queue" => queue::autocomplete(&ctx, &autocomplete).await,
+                "remove" | "jump" => list::autocomplete(&ctx, &autocomplete).await,
+                "sfx" => sfx::autocomplete(&ctx, &autocomplete).await,
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+
+    async fn voice_state_update(
+        &self,
+        ctx: Context,
+        guild_id: Option<GuildId>,
+        old: Option<VoiceState>,
+        new: VoiceState,
+    ) {
+        if let Some(guild_id) = guild_id {
+            follow::check(&ctx, guild_id, &new).await;
+            jingle::greet(&ctx, guild_id, old.as_ref(), &new).await;
+            alone::check(&ctx, guild_id).await;
         }
     }
 }
@@ -128,6 +694,9 @@ async fn main() {
         .parse()
         .expect("application id is not a valid id");
 
+    // Where guild settings and other persistent data are kept.
+    let data_dir = PathBuf::from(env::var("DATA_DIR").unwrap_or_else(|_| "data".to_string()));
+
     // Build our client.
     let mut client = Client::builder(token)
         .event_handler(Handler)
@@ -137,6 +706,27 @@ async fn main() {
         .await
         .expect("Error creating client");
 
+    {
+        let mut data = client.data.write().await;
+        data.insert::<DataDir>(data_dir.clone());
+        data.insert::<Settings>(Arc::new(RwLock::new(Settings::load(
+            data_dir.join("settings.json"),
+        ))));
+        data.insert::<History>(Arc::new(RwLock::new(HashMap::new())));
+        data.insert::<Announce>(Arc::new(RwLock::new(HashMap::new())));
+        data.insert::<NowPlaying>(Arc::new(RwLock::new(HashMap::new())));
+        data.insert::<SleepTimers>(Arc::new(Mutex::new(HashMap::new())));
+        data.insert::<AloneTimers>(Arc::new(Mutex::new(HashMap::new())));
+        data.insert::<Following>(Arc::new(RwLock::new(HashMap::new())));
+        data.insert::<Mixing>(Arc::new(Mutex::new(HashMap::new())));
+        data.insert::<Votes>(Arc::new(Mutex::new(Votes::new())));
+        data.insert::<RateLimits>(Arc::new(Mutex::new(HashMap::new())));
+        data.insert::<Progress>(Arc::new(Mutex::new(Progress::new())));
+        data.insert::<Schedules>(Arc::new(Mutex::new(Schedules::load(
+            data_dir.join("schedules.json"),
+        ))));
+    }
+
     // Finally, start a single shard, and start listening to events.
     //
     // Shards will automatically attempt to reconnect, and will perform
@@ -0,0 +1,112 @@
+use serenity::{async_trait, client::Context, model::id::GuildId, prelude::TypeMapKey};
+use songbird::input::Input;
+use songbird::tracks::TrackHandle;
+use songbird::{Call, Event, EventContext, EventHandler as VoiceEventHandler, TrackEvent};
+use std::{collections::HashMap, sync::Arc};
+use tokio::sync::Mutex;
+
+/// Music volume while something plays over it.
+const DUCKED_VOLUME: f32 = 0.3;
+
+/// Number of sounds currently playing over the music, per guild.
+pub struct Mixing;
+
+impl TypeMapKey for Mixing {
+    type Value = Arc<Mutex<HashMap<GuildId, usize>>>;
+}
+
+/// Marks tracks played over the music, which are not part of the queue.
+pub struct Overlay;
+
+impl TypeMapKey for Overlay {
+    type Value = ();
+}
+
+pub async fn is_overlay(track: &TrackHandle) -> bool {
+    track.typemap().read().await.contains_key::<Overlay>()
+}
+
+struct Unduck {
+    guild_id: GuildId,
+    handler_lock: Arc<Mutex<Call>>,
+    ctx: Arc<Mutex<Context>>,
+}
+
+#[async_trait]
+impl VoiceEventHandler for Unduck {
+    async fn act(&self, _ctx: &EventContext<'_>) -> Option<Event> {
+        let app_ctx = self.ctx.lock().await.clone();
+        end(&app_ctx, self.guild_id, &self.handler_lock).await;
+        None
+    }
+}
+
+/// Plays `source` on top of the queue at `volume`, turning the music down
+/// until it is over.
+pub async fn play_over(
+    ctx: &Context,
+    guild_id: GuildId,
+    handler_lock: &Arc<Mutex<Call>>,
+    source: Input,
+    volume: f32,
+) -> TrackHandle {
+    {
+        let data = ctx.data.read().await;
+        let mut mixing = data
+            .get::<Mixing>()
+            .expect("Mixing placed in at initialisation.")
+            .lock()
+            .await;
+
+        *mixing.entry(guild_id).or_default() += 1;
+    }
+
+    let mut handler = handler_lock.lock().await;
+    if let Some(track) = handler.queue().current() {
+        let _ = track.set_volume(DUCKED_VOLUME);
+    }
+
+    let track = handler.play_source(source);
+    drop(handler);
+
+    track.typemap().write().await.insert::<Overlay>(());
+    let _ = track.set_volume(volume);
+
+    let unduck = Unduck {
+        guild_id,
+        handler_lock: handler_lock.clone(),
+        ctx: Arc::new(Mutex::new(ctx.clone())),
+    };
+    // Too short to even get an event registered.
+    if track
+        .add_event(Event::Track(TrackEvent::End), unduck)
+        .is_err()
+    {
+        end(ctx, guild_id, handler_lock).await;
+    }
+
+    track
+}
+
+/// Brings the music back up once nothing plays over it anymore.
+async fn end(ctx: &Context, guild_id: GuildId, handler_lock: &Arc<Mutex<Call>>) {
+    {
+        let data = ctx.data.read().await;
+        let mut mixing = data
+            .get::<Mixing>()
+            .expect("Mixing placed in at initialisation.")
+            .lock()
+            .await;
+
+        let playing = mixing.entry(guild_id).or_default();
+        *playing = playing.saturating_sub(1);
+        if *playing > 0 {
+            return;
+        }
+        mixing.remove(&guild_id);
+    }
+
+    if let Some(track) = handler_lock.lock().await.queue().current() {
+        let _ = track.set_volume(1.0);
+    }
+}
@@ -0,0 +1,178 @@
+use crate::cmd::{check_msg, now_playing, TrackInfo};
+use crate::i18n;
+use serenity::{
+    client::Context,
+    model::channel::Message,
+    model::id::{ChannelId, GuildId, MessageId},
+    prelude::TypeMapKey,
+};
+use songbird::tracks::TrackHandle;
+use std::{collections::HashMap, sync::Arc, time::Duration};
+use tokio::{sync::Mutex, time::MissedTickBehavior};
+
+/// How often a now playing message gets refreshed.
+const REFRESH: Duration = Duration::from_secs(12);
+/// Gap between two edits, keeps us well under the global rate limit no
+/// matter how many guilds are playing.
+const EDIT_GAP: Duration = Duration::from_millis(100);
+
+#[derive(Clone)]
+struct Tracked {
+    chan_id: ChannelId,
+    message_id: MessageId,
+    track: TrackHandle,
+    info: TrackInfo,
+}
+
+/// Now playing messages being kept up to date, one per guild.
+pub struct Progress {
+    messages: HashMap<GuildId, Tracked>,
+    running: bool,
+}
+
+impl TypeMapKey for Progress {
+    type Value = Arc<Mutex<Progress>>;
+}
+
+impl Progress {
+    pub fn new() -> Self {
+        Self {
+            messages: HashMap::new(),
+            running: false,
+        }
+    }
+}
+
+/// Keeps `message` showing the progress of `track` until it ends.
+pub async fn follow(
+    ctx: &Context,
+    guild_id: GuildId,
+    message: &Message,
+    track: TrackHandle,
+    info: TrackInfo,
+) {
+    // Live tracks have nothing to show.
+    if track.metadata().duration.unwrap_or_default() == Duration::default() {
+        return;
+    }
+
+    let data = ctx.data.read().await;
+    let mut progress = data
+        .get::<Progress>()
+        .expect("Progress placed in at initialisation.")
+        .lock()
+        .await;
+
+    progress.messages.insert(
+        guild_id,
+        Tracked {
+            chan_id: message.channel_id,
+            message_id: message.id,
+            track,
+            info,
+        },
+    );
+}
+
+pub async fn forget(ctx: &Context, guild_id: GuildId) {
+    let data = ctx.data.read().await;
+    let mut progress = data
+        .get::<Progress>()
+        .expect("Progress placed in at initialisation.")
+        .lock()
+        .await;
+
+    progress.messages.remove(&guild_id);
+}
+
+/// Starts refreshing now playing messages, unless it is already running.
+pub async fn start(ctx: &Context) {
+    {
+        let data = ctx.data.read().await;
+        let mut progress = data
+            .get::<Progress>()
+            .expect("Progress placed in at initialisation.")
+            .lock()
+            .await;
+
+        if progress.running {
+            return;
+        }
+        progress.running = true;
+    }
+
+    let ctx = ctx.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(REFRESH);
+        // A slow pass over many guilds pushes the next one back.
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            interval.tick().await;
+
+            let guilds = {
+                let data = ctx.data.read().await;
+                let progress = data
+                    .get::<Progress>()
+                    .expect("Progress placed in at initialisation.")
+                    .lock()
+                    .await;
+
+                progress.messages.keys().copied().collect::<Vec<GuildId>>()
+            };
+
+            for guild_id in guilds {
+                refresh(&ctx, guild_id).await;
+                tokio::time::sleep(EDIT_GAP).await;
+            }
+        }
+    });
+}
+
+async fn refresh(ctx: &Context, guild_id: GuildId) {
+    let tracked = {
+        let data = ctx.data.read().await;
+        let progress = data
+            .get::<Progress>()
+            .expect("Progress placed in at initialisation.")
+            .lock()
+            .await;
+
+        match progress.messages.get(&guild_id) {
+            Some(tracked) => tracked.clone(),
+            None => return,
+        }
+    };
+
+    // Fails once the track is gone.
+    let position = match tracked.track.get_info().await {
+        Ok(state) => state.position,
+        Err(_) => {
+            let data = ctx.data.read().await;
+            let mut progress = data
+                .get::<Progress>()
+                .expect("Progress placed in at initialisation.")
+                .lock()
+                .await;
+
+            // Unless a new track already took its place.
+            if let Some(t) = progress.messages.get(&guild_id) {
+                if t.track.uuid() == tracked.track.uuid() {
+                    progress.messages.remove(&guild_id);
+                }
+            }
+            return;
+        }
+    };
+
+    let metadata = tracked.track.metadata().clone();
+    let lang = i18n::guild_lang(ctx, guild_id).await;
+    check_msg(
+        tracked
+            .chan_id
+            .edit_message(&ctx.http, tracked.message_id, |m| {
+                m.embed(|e| now_playing(e, &metadata, tracked.info, position, lang))
+            })
+            .await,
+    );
+}
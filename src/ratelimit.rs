@@ -0,0 +1,125 @@
+use crate::cmd::{check_msg, interaction_reply, Invocation};
+use crate::i18n::{self, tr};
+use crate::settings;
+use serenity::{
+    client::Context,
+    model::id::{GuildId, UserId},
+    prelude::TypeMapKey,
+};
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Arc,
+    time::Duration,
+};
+use tokio::{sync::Mutex, time::Instant};
+
+/// When each user last ran each command, per guild.
+pub struct RateLimits;
+
+impl TypeMapKey for RateLimits {
+    type Value = Arc<Mutex<HashMap<(GuildId, UserId, String), Uses>>>;
+}
+
+/// Recent uses of a command by someone.
+#[derive(Default)]
+pub struct Uses {
+    /// Window uses are counted over.
+    per: Duration,
+    times: VecDeque<Instant>,
+}
+
+impl Uses {
+    /// Forgets uses that left the window, returns whether any are left.
+    fn expire(&mut self, now: Instant) -> bool {
+        while self
+            .times
+            .front()
+            .is_some_and(|t| now.duration_since(*t) >= self.per)
+        {
+            self.times.pop_front();
+        }
+        !self.times.is_empty()
+    }
+}
+
+/// Counts a use of `command` by whoever is behind `cmd`, replying and
+/// returning false when they ran it too often lately.
+pub async fn check(ctx: &Context, cmd: &Invocation<'_>, command: &str) -> bool {
+    let guild_id = match cmd.guild_id() {
+        Some(guild_id) => guild_id,
+        None => return true,
+    };
+
+    let limit = match settings::get(ctx, guild_id).await.rate_limits.get(command) {
+        Some(limit) if limit.uses > 0 => *limit,
+        _ => return true,
+    };
+    let per = Duration::from_secs(limit.per);
+    let now = Instant::now();
+
+    let wait = {
+        let data = ctx.data.read().await;
+        let mut limits = data
+            .get::<RateLimits>()
+            .expect("RateLimits placed in at initialisation.")
+            .lock()
+            .await;
+
+        // Nobody needs remembering once their window is over.
+        limits.retain(|_, uses| uses.expire(now));
+
+        let uses = limits
+            .entry((guild_id, cmd.user().id, command.to_string()))
+            .or_default();
+        uses.per = per;
+
+        match uses.times.len() as u64 >= limit.uses {
+            true => uses
+                .times
+                .front()
+                .map(|t| per.saturating_sub(now.duration_since(*t))),
+            false => {
+                uses.times.push_back(now);
+                None
+            }
+        }
+    };
+
+    match wait {
+        Some(wait) => {
+            let lang = i18n::lang(ctx, Some(guild_id), cmd.locale()).await;
+            check_msg(
+                cmd.create_interaction_response(&ctx.http, |response| {
+                    interaction_reply(
+                        response,
+                        tr(lang, "ratelimit.wait", &[&command, &(wait.as_secs() + 1)]),
+                        true,
+                    )
+                })
+                .await,
+            );
+            false
+        }
+        None => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn uses_expire_with_their_window() {
+        let now = Instant::now();
+        let mut uses = Uses {
+            per: Duration::from_secs(30),
+            times: VecDeque::from([now, now + Duration::from_secs(20)]),
+        };
+
+        assert!(uses.expire(now + Duration::from_secs(29)));
+        assert_eq!(uses.times.len(), 2);
+        assert!(uses.expire(now + Duration::from_secs(30)));
+        assert_eq!(uses.times.len(), 1);
+        assert!(!uses.expire(now + Duration::from_secs(50)));
+    }
+}
@@ -0,0 +1,236 @@
+use crate::i18n::{tr, Lang};
+use serde::{Deserialize, Serialize};
+use serenity::{
+    client::Context,
+    model::id::{ChannelId, GuildId},
+    prelude::{Mentionable, TypeMapKey},
+};
+use std::{collections::HashMap, fs, path::PathBuf, sync::Arc};
+use tokio::sync::RwLock;
+
+/// What it takes for a vote to pass.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VoteRule {
+    /// More yeps than nopes among those who voted.
+    Majority,
+    /// A share of everyone allowed to vote said yep, in percent.
+    Percent(u64),
+    /// That many people said yep.
+    Count(u64),
+}
+
+impl VoteRule {
+    pub fn describe(&self, lang: Lang) -> String {
+        match self {
+            Self::Majority => tr(lang, "rule.majority", &[]),
+            Self::Percent(p) => tr(lang, "rule.percent", &[p]),
+            Self::Count(n) => tr(lang, "rule.count", &[n]),
+        }
+    }
+}
+
+/// What happens when someone who is not a DJ runs a privileged command.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Privileged {
+    Vote,
+    Refuse,
+}
+
+impl Privileged {
+    pub fn describe(&self, lang: Lang) -> String {
+        match self {
+            Self::Vote => tr(lang, "privileged.vote", &[]),
+            Self::Refuse => tr(lang, "privileged.refuse", &[]),
+        }
+    }
+}
+
+/// Where announcements such as now playing messages go.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Announcements {
+    /// Where `/join` was run.
+    Invoked,
+    /// A fixed text channel, or voice channel's text chat.
+    Channel(u64),
+    /// The text chat of whichever voice channel the bot is in.
+    Voice,
+    Off,
+}
+
+impl Announcements {
+    pub fn describe(&self, lang: Lang) -> String {
+        match self {
+            Self::Invoked => tr(lang, "announce.invoked", &[]),
+            Self::Channel(c) => tr(lang, "announce.channel", &[&ChannelId(*c).mention()]),
+            Self::Voice => tr(lang, "announce.voice", &[]),
+            Self::Off => tr(lang, "announce.off", &[]),
+        }
+    }
+}
+
+/// What becomes of the now playing message once the next track starts.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NowPlayingMode {
+    /// Stays, the next track getting a message of its own.
+    New,
+    /// Edited to show the next track.
+    Edit,
+    /// Deleted, the next track getting a message of its own.
+    Replace,
+}
+
+impl NowPlayingMode {
+    pub fn describe(&self, lang: Lang) -> String {
+        match self {
+            Self::New => tr(lang, "nowplaying.new", &[]),
+            Self::Edit => tr(lang, "nowplaying.edit", &[]),
+            Self::Replace => tr(lang, "nowplaying.replace", &[]),
+        }
+    }
+}
+
+/// How often someone may run a command.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct RateLimit {
+    pub uses: u64,
+    /// Window the uses are counted over, in seconds.
+    pub per: u64,
+}
+
+/// Per guild configuration, persisted across restarts.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GuildSettings {
+    /// Language replies are in, each user's own if unset.
+    pub language: Option<Lang>,
+    pub autoplay: bool,
+    pub announcements: Announcements,
+    pub now_playing: NowPlayingMode,
+    /// Seconds to wait before leaving a voice channel with nobody in it.
+    pub alone_timeout: u64,
+    /// Minutes without anything playing before leaving, 0 to stay forever.
+    pub idle_timeout: u64,
+    /// Whether upcoming tracks are announced out loud in the voice channel.
+    pub spoken_announcements: bool,
+    /// Whether members get their jingle played when joining.
+    pub jingles: bool,
+    /// Longest a jingle may play, in seconds.
+    pub jingle_length: u64,
+    /// Jingle volume, in percent.
+    pub jingle_volume: u64,
+    pub vote_rule: VoteRule,
+    /// Seconds a vote stays open.
+    pub vote_duration: u64,
+    /// Roles whose members get their way without a vote.
+    pub dj_roles: Vec<u64>,
+    pub privileged: Privileged,
+    /// Per command limits, by command name.
+    pub rate_limits: HashMap<String, RateLimit>,
+}
+
+impl Default for GuildSettings {
+    fn default() -> Self {
+        Self {
+            language: None,
+            autoplay: false,
+            announcements: Announcements::Invoked,
+            now_playing: NowPlayingMode::New,
+            alone_timeout: 120,
+            idle_timeout: 10,
+            spoken_announcements: false,
+            jingles: true,
+            jingle_length: 5,
+            jingle_volume: 50,
+            vote_rule: VoteRule::Majority,
+            vote_duration: 15,
+            dj_roles: Vec::new(),
+            privileged: Privileged::Vote,
+            rate_limits: HashMap::from([
+                ("queue".to_string(), RateLimit { uses: 5, per: 60 }),
+                ("skip".to_string(), RateLimit { uses: 1, per: 30 }),
+            ]),
+        }
+    }
+}
+
+/// Where guild settings and other persistent data are kept.
+pub struct DataDir;
+
+impl TypeMapKey for DataDir {
+    type Value = PathBuf;
+}
+
+pub async fn data_dir(ctx: &Context) -> PathBuf {
+    let data = ctx.data.read().await;
+    data.get::<DataDir>()
+        .expect("DataDir placed in at initialisation.")
+        .clone()
+}
+
+pub struct Settings {
+    path: PathBuf,
+    guilds: HashMap<u64, GuildSettings>,
+}
+
+impl TypeMapKey for Settings {
+    type Value = Arc<RwLock<Settings>>;
+}
+
+impl Settings {
+    pub fn load(path: PathBuf) -> Self {
+        let guilds = match fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|why| {
+                println!("Err parsing settings {:?}: {:?}", path, why);
+                HashMap::new()
+            }),
+            Err(_) => HashMap::new(),
+        };
+
+        Self { path, guilds }
+    }
+
+    fn save(&self) {
+        if let Some(dir) = self.path.parent() {
+            let _ = fs::create_dir_all(dir);
+        }
+
+        let content = serde_json::to_string_pretty(&self.guilds).unwrap();
+        if let Err(why) = fs::write(&self.path, content) {
+            println!("Err saving settings {:?}: {:?}", self.path, why);
+        }
+    }
+}
+
+pub async fn get(ctx: &Context, guild_id: GuildId) -> GuildSettings {
+    let data = ctx.data.read().await;
+    let settings = data
+        .get::<Settings>()
+        .expect("Settings placed in at initialisation.")
+        .read()
+        .await;
+
+    settings
+        .guilds
+        .get(&guild_id.0)
+        .cloned()
+        .unwrap_or_default()
+}
+
+pub async fn update<F>(ctx: &Context, guild_id: GuildId, f: F)
+where
+    F: FnOnce(&mut GuildSettings),
+{
+    let data = ctx.data.read().await;
+    let mut settings = data
+        .get::<Settings>()
+        .expect("Settings placed in at initialisation.")
+        .write()
+        .await;
+
+    f(settings.guilds.entry(guild_id.0).or_default());
+    settings.save();
+}
@@ -0,0 +1,54 @@
+use serenity::{client::Context, model::channel::ChannelType, model::id::ChannelId};
+
+/// Longest topic a stage instance accepts.
+const TOPIC_LIMIT: usize = 120;
+
+/// Gets the bot on stage as a speaker when `channel_id` is a stage channel,
+/// asking to speak when it is not allowed to move itself there.
+pub async fn speak(ctx: &Context, channel_id: ChannelId) {
+    let channel = match ctx.cache.guild_channel(channel_id).await {
+        Some(channel) if channel.kind == ChannelType::Stage => channel,
+        _ => return,
+    };
+
+    if let Err(why) = channel
+        .edit_own_voice_state(&ctx.http, |v| v.suppress(false))
+        .await
+    {
+        println!("Err unsuppressing on stage: {:?}", why);
+        if let Err(why) = channel
+            .edit_own_voice_state(&ctx.http, |v| v.request_to_speak(true))
+            .await
+        {
+            println!("Err requesting to speak: {:?}", why);
+        }
+    }
+}
+
+/// Sets the topic of the stage `channel_id` to `title`, opening the stage
+/// if nobody did yet. Does nothing for plain voice channels.
+pub async fn topic(ctx: &Context, channel_id: ChannelId, title: &str) {
+    match ctx.cache.guild_channel(channel_id).await {
+        Some(channel) if channel.kind == ChannelType::Stage => {}
+        _ => return,
+    }
+
+    let topic = title.chars().take(TOPIC_LIMIT).collect::<String>();
+
+    let result = match channel_id.get_stage_instance(&ctx.http).await {
+        Ok(_) => {
+            channel_id
+                .edit_stage_instance(&ctx.http, |i| i.topic(&topic))
+                .await
+        }
+        Err(_) => {
+            channel_id
+                .create_stage_instance(&ctx.http, |i| i.channel_id(channel_id.0).topic(&topic))
+                .await
+        }
+    };
+
+    if let Err(why) = result {
+        println!("Err setting stage topic: {:?}", why);
+    }
+}
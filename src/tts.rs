@@ -0,0 +1,84 @@
+use crate::i18n::Lang;
+use serenity::async_trait;
+use serenity::model::id::GuildId;
+use songbird::input::Input;
+use songbird::tracks::TrackHandle;
+use songbird::{Event, EventContext, EventHandler as VoiceEventHandler, TrackEvent};
+use std::{
+    env, fs,
+    path::PathBuf,
+    sync::atomic::{AtomicU64, Ordering},
+};
+use tokio::process::Command;
+
+/// Numbers speech files, announcements may overlap.
+static NEXT: AtomicU64 = AtomicU64::new(0);
+
+/// Renders `text` to speech with espeak in the voice for `lang`, ready to be
+/// played through ffmpeg. The file it is read from must be handed to
+/// `remove_after` once playing.
+pub async fn speech(guild_id: GuildId, text: &str, lang: Lang) -> Option<(Input, PathBuf)> {
+    let path = env::temp_dir().join(format!(
+        "tbpi-tts-{}-{}.wav",
+        guild_id,
+        NEXT.fetch_add(1, Ordering::Relaxed)
+    ));
+
+    let status = Command::new("espeak")
+        .args(["-v", lang.code()])
+        .arg("-w")
+        .arg(&path)
+        .arg("--")
+        .arg(text)
+        .status()
+        .await;
+
+    match status {
+        Ok(status) if status.success() => {}
+        Ok(status) => {
+            println!("Err running espeak: {}", status);
+            remove(&path);
+            return None;
+        }
+        Err(why) => {
+            println!("Err running espeak: {:?}", why);
+            return None;
+        }
+    }
+
+    match songbird::ffmpeg(&path).await {
+        Ok(source) => Some((source, path)),
+        Err(why) => {
+            println!("Err starting speech: {:?}", why);
+            remove(&path);
+            None
+        }
+    }
+}
+
+/// Removes the speech file at `path` once `track` is done playing it.
+pub fn remove_after(track: &TrackHandle, path: PathBuf) {
+    // Too short to even get an event registered.
+    if track
+        .add_event(Event::Track(TrackEvent::End), Cleanup(path.clone()))
+        .is_err()
+    {
+        remove(&path);
+    }
+}
+
+fn remove(path: &PathBuf) {
+    if let Err(why) = fs::remove_file(path) {
+        println!("Err removing speech {:?}: {:?}", path, why);
+    }
+}
+
+struct Cleanup(PathBuf);
+
+#[async_trait]
+impl VoiceEventHandler for Cleanup {
+    async fn act(&self, _ctx: &EventContext<'_>) -> Option<Event> {
+        remove(&self.0);
+        None
+    }
+}
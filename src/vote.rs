@@ -0,0 +1,674 @@
+use crate::cmd::jump::jump_to;
+use crate::cmd::leave;
+use crate::cmd::remove::{remove_track, stop_queue, Removed};
+use crate::cmd::shuffle::shuffle_upcoming;
+use crate::cmd::{check_msg, interaction_reply, listeners, Invocation, Res};
+use crate::i18n::{self, tr, Lang};
+use crate::progress;
+use crate::settings::{self, VoteRule};
+use serenity::{
+    builder::{CreateActionRow, CreateButton, CreateEmbed},
+    client::Context,
+    futures::StreamExt,
+    model::id::{ChannelId, GuildId, UserId},
+    model::interactions::message_component::ButtonStyle,
+    model::interactions::InteractionResponseType,
+    prelude::{Mentionable, TypeMapKey},
+};
+use songbird::tracks::TrackHandle;
+use songbird::Call;
+use std::time::Duration;
+use std::{collections::HashMap, sync::Arc};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+use tokio::sync::Mutex;
+use tokio::time::Instant;
+
+/// How long someone whose vote failed has to wait before starting another.
+const VOTE_COOLDOWN: Duration = Duration::from_secs(60);
+
+/// What a vote is about.
+#[derive(Clone)]
+pub enum Motion {
+    Skip(TrackHandle),
+    Stop,
+    Remove(TrackHandle),
+    Clear,
+    Jump(TrackHandle),
+    Shuffle,
+    Leave,
+}
+
+impl Motion {
+    fn same(&self, other: &Motion) -> bool {
+        match (self, other) {
+            (Self::Skip(a), Self::Skip(b))
+            | (Self::Remove(a), Self::Remove(b))
+            | (Self::Jump(a), Self::Jump(b)) => a.uuid() == b.uuid(),
+            (Self::Stop, Self::Stop)
+            | (Self::Clear, Self::Clear)
+            | (Self::Shuffle, Self::Shuffle)
+            | (Self::Leave, Self::Leave) => true,
+            _ => false,
+        }
+    }
+
+    /// Whether whoever asked for it has to vote too, rather than their yep
+    /// going in right away. Purging the queue deserves a second click.
+    fn needs_confirmation(&self) -> bool {
+        matches!(self, Self::Stop)
+    }
+
+    fn describe(&self, lang: Lang) -> String {
+        match self {
+            Self::Skip(track) => tr(lang, "vote.skip", &[&title(track, lang)]),
+            Self::Stop => tr(lang, "vote.stop", &[]),
+            Self::Remove(track) => tr(lang, "vote.remove", &[&title(track, lang)]),
+            Self::Clear => tr(lang, "vote.clear", &[]),
+            Self::Jump(track) => tr(lang, "vote.jump", &[&title(track, lang)]),
+            Self::Shuffle => tr(lang, "vote.shuffle", &[]),
+            Self::Leave => tr(lang, "vote.leave", &[]),
+        }
+    }
+
+    /// Does what was voted, returns what happened or why nothing did.
+    async fn carry(
+        &self,
+        ctx: &Context,
+        guild_id: GuildId,
+        handler_lock: &Arc<Mutex<Call>>,
+        lang: Lang,
+    ) -> String {
+        // Leaving needs the call for itself.
+        if let Self::Leave = self {
+            return match leave::disconnect(ctx, guild_id).await {
+                true => tr(lang, "vote.left", &[]),
+                false => tr(lang, "not_in_voice", &[]),
+            };
+        }
+
+        let handler = handler_lock.lock().await;
+        let queue = handler.queue();
+
+        match self {
+            Self::Skip(track) => match queue.current() {
+                Some(current) if current.uuid() == track.uuid() => {
+                    let _ = queue.skip();
+                    tr(lang, "vote.skipped", &[&title(track, lang)])
+                }
+                _ => tr(lang, "vote.not_playing", &[&title(track, lang)]),
+            },
+            Self::Stop => {
+                stop_queue(queue).await;
+                progress::forget(ctx, guild_id).await;
+                ctx.reset_presence().await;
+                tr(lang, "vote.stopped", &[])
+            }
+            Self::Remove(track) => match remove_track(queue, track).await {
+                true => tr(lang, "vote.removed", &[&title(track, lang)]),
+                false => tr(lang, "vote.not_queued", &[&title(track, lang)]),
+            },
+            Self::Clear => {
+                let upcoming = queue.modify_queue(|queue| match queue.len() {
+                    0 | 1 => Vec::new(),
+                    _ => queue.drain(1..).map(|q| q.handle()).collect::<Vec<_>>(),
+                });
+                for track in upcoming.iter() {
+                    track.typemap().write().await.insert::<Removed>(());
+                    let _ = track.stop();
+                }
+                tr(lang, "vote.cleared", &[&upcoming.len()])
+            }
+            Self::Jump(track) => match jump_to(queue, track) {
+                true => tr(lang, "vote.jumped", &[&title(track, lang)]),
+                false => tr(lang, "vote.not_queued", &[&title(track, lang)]),
+            },
+            Self::Shuffle => match shuffle_upcoming(queue) {
+                true => tr(lang, "vote.shuffled", &[]),
+                false => tr(lang, "vote.too_few", &[]),
+            },
+            Self::Leave => unreachable!(),
+        }
+    }
+}
+
+fn title(track: &TrackHandle, lang: Lang) -> String {
+    track
+        .metadata()
+        .title
+        .clone()
+        .unwrap_or_else(|| tr(lang, "no_title", &[]))
+}
+
+enum VoteBtn {
+    Yep,
+    Nope,
+}
+
+impl VoteBtn {
+    fn label(&self, lang: Lang) -> String {
+        match self {
+            Self::Yep => tr(lang, "vote.yep", &[]),
+            Self::Nope => tr(lang, "vote.nope", &[]),
+        }
+    }
+
+    fn custom_id(&self) -> &'static str {
+        match self {
+            Self::Yep => "vote_yep",
+            Self::Nope => "vote_nope",
+        }
+    }
+
+    fn button(&self, lang: Lang) -> CreateButton {
+        let mut b = CreateButton::default();
+        b.custom_id(self.custom_id());
+        b.label(self.label(lang));
+        match self {
+            VoteBtn::Nope => b.style(ButtonStyle::Danger),
+            VoteBtn::Yep => b.style(ButtonStyle::Primary),
+        };
+        b
+    }
+
+    fn action_row(lang: Lang) -> CreateActionRow {
+        let mut ar = CreateActionRow::default();
+        ar.add_button(VoteBtn::Nope.button(lang));
+        ar.add_button(VoteBtn::Yep.button(lang));
+        ar
+    }
+}
+
+struct Tally {
+    lang: Lang,
+    initiator: String,
+    motion: String,
+    rule: VoteRule,
+    duration: Duration,
+    /// How many people are allowed to vote.
+    eligible: usize,
+    yep: HashMap<UserId, String>,
+    nope: HashMap<UserId, String>,
+}
+
+impl Tally {
+    fn embed<'a>(&self, e: &'a mut CreateEmbed) -> &'a mut CreateEmbed {
+        let names = |members: &HashMap<UserId, String>| match members.is_empty() {
+            true => "-".to_string(),
+            false => members
+                .values()
+                .cloned()
+                .collect::<Vec<String>>()
+                .join("\n"),
+        };
+
+        e.title(tr(
+            self.lang,
+            "vote.title",
+            &[&self.initiator, &self.motion],
+        ))
+        .description(tr(
+            self.lang,
+            "vote.description",
+            &[&self.duration.as_secs(), &self.rule.describe(self.lang)],
+        ))
+        .field(VoteBtn::Nope.label(self.lang), names(&self.nope), true)
+        .field(VoteBtn::Yep.label(self.lang), names(&self.yep), true)
+    }
+
+    fn passes(&self, yep: usize, nope: usize) -> bool {
+        match self.rule {
+            VoteRule::Majority => yep > nope,
+            VoteRule::Percent(p) => yep > 0 && yep as u64 * 100 >= p * self.eligible as u64,
+            // Asking for more people than there are would never pass.
+            VoteRule::Count(n) => yep > 0 && yep as u64 >= n.min(self.eligible as u64),
+        }
+    }
+
+    fn passed(&self) -> bool {
+        self.passes(self.yep.len(), self.nope.len())
+    }
+
+    /// The result, once whoever did not vote yet can no longer change it.
+    fn outcome(&self) -> Option<bool> {
+        let (yep, nope) = (self.yep.len(), self.nope.len());
+        let undecided = self.eligible.saturating_sub(yep + nope);
+
+        if self.passes(yep, nope + undecided) {
+            Some(true)
+        } else if !self.passes(yep + undecided, nope) {
+            Some(false)
+        } else {
+            None
+        }
+    }
+}
+
+/// Users allowed to vote on what happens in `channel_id`, those who can
+/// actually hear it.
+async fn voters(ctx: &Context, guild_id: GuildId, channel_id: ChannelId) -> Vec<UserId> {
+    let deafened = ctx
+        .cache
+        .guild_field(guild_id, |g| {
+            g.voice_states
+                .values()
+                .filter(|v| v.deaf || v.self_deaf)
+                .map(|v| v.user_id)
+                .collect::<Vec<UserId>>()
+        })
+        .await
+        .unwrap_or_default();
+
+    let listeners = listeners(ctx, channel_id)
+        .await
+        .iter()
+        .map(|m| m.user.id)
+        .collect::<Vec<UserId>>();
+
+    hearing(&listeners, &deafened)
+}
+
+/// Those of `listeners` who are not `deafened`.
+fn hearing(listeners: &[UserId], deafened: &[UserId]) -> Vec<UserId> {
+    listeners
+        .iter()
+        .filter(|u| !deafened.contains(u))
+        .copied()
+        .collect()
+}
+
+/// Votes going on and who cannot start one for a while, per guild.
+pub struct Votes {
+    running: HashMap<GuildId, Running>,
+    cooldowns: HashMap<(GuildId, UserId), Instant>,
+}
+
+impl TypeMapKey for Votes {
+    type Value = Arc<Mutex<Votes>>;
+}
+
+impl Votes {
+    pub fn new() -> Self {
+        Self {
+            running: HashMap::new(),
+            cooldowns: HashMap::new(),
+        }
+    }
+}
+
+struct Running {
+    motion: Motion,
+    voters: Vec<UserId>,
+    /// Link to the vote message, once it is posted.
+    link: Option<String>,
+    /// Yeps coming from people running the same command again.
+    yeps: UnboundedSender<(UserId, String)>,
+}
+
+/// Puts `motion` to the vote of everyone listening along, carrying it out
+/// right away when nobody else is there to ask, unless it needs confirming.
+/// Only one vote runs at a time in a guild. The interaction must have been deferred already.
+pub async fn hold(ctx: &Context, cmd: &Invocation<'_>, motion: Motion) -> Res {
+    let manager = songbird::get(ctx)
+        .await
+        .expect("Songbird Voice client placed in at initialisation.")
+        .clone();
+
+    let guild_id = cmd.guild_id().unwrap();
+    let user = cmd.user();
+    let lang = i18n::lang(ctx, Some(guild_id), cmd.locale()).await;
+    let handler_lock = match manager.get(guild_id) {
+        Some(handler_lock) => handler_lock,
+        None => {
+            check_msg(
+                cmd.edit_original_interaction_response(&ctx.http, |response| {
+                    response.content(tr(lang, "not_playing", &[]))
+                })
+                .await,
+            );
+            return Ok(());
+        }
+    };
+
+    let channel = handler_lock.lock().await.current_channel();
+    let voters = match channel {
+        Some(channel) => voters(ctx, guild_id, ChannelId(channel.0)).await,
+        None => Vec::new(),
+    };
+    let settings = settings::get(ctx, guild_id).await;
+
+    let (yeps, mut rx) = unbounded_channel();
+    let refusal = {
+        let data = ctx.data.read().await;
+        let mut votes = data
+            .get::<Votes>()
+            .expect("Votes placed in at initialisation.")
+            .lock()
+            .await;
+
+        votes.cooldowns.retain(|_, until| *until > Instant::now());
+
+        if let Some(running) = votes.running.get(&guild_id) {
+            let link = running.link.clone().unwrap_or_default();
+            if running.motion.same(&motion) && running.voters.contains(&user.id) {
+                let _ = running.yeps.send((user.id, user.name.clone()));
+                Some(tr(lang, "vote.joined", &[&link]))
+            } else {
+                Some(tr(lang, "vote.busy", &[&link]))
+            }
+        } else if let Some(until) = votes.cooldowns.get(&(guild_id, user.id)) {
+            Some(tr(
+                lang,
+                "vote.cooldown",
+                &[&(until.duration_since(Instant::now()).as_secs() + 1)],
+            ))
+        } else {
+            votes.running.insert(
+                guild_id,
+                Running {
+                    motion: motion.clone(),
+                    voters: voters.clone(),
+                    link: None,
+                    yeps,
+                },
+            );
+            None
+        }
+    };
+
+    if let Some(content) = refusal {
+        check_msg(
+            cmd.edit_original_interaction_response(&ctx.http, |response| response.content(content))
+                .await,
+        );
+        return Ok(());
+    }
+
+    let mut tally = Tally {
+        lang,
+        initiator: user.name.clone(),
+        motion: motion.describe(lang),
+        rule: settings.vote_rule,
+        duration: Duration::from_secs(settings.vote_duration),
+        eligible: voters.len(),
+        yep: HashMap::new(),
+        nope: HashMap::new(),
+    };
+    if voters.contains(&user.id) && !motion.needs_confirmation() {
+        tally.yep.insert(user.id, user.name.clone());
+    }
+
+    let content = match voters.is_empty() || tally.outcome() == Some(true) {
+        true => {
+            let content = motion.carry(ctx, guild_id, &handler_lock, lang).await;
+            check_msg(
+                cmd.edit_original_interaction_response(&ctx.http, |response| {
+                    response.content(format!("{} ({})", content, user.mention()))
+                })
+                .await,
+            );
+            None
+        }
+        false => {
+            let passed = run(ctx, cmd, &voters, &mut tally, &mut rx).await;
+            Some(match passed {
+                true => tr(
+                    lang,
+                    "vote.passed",
+                    &[
+                        &tally.motion,
+                        &motion.carry(ctx, guild_id, &handler_lock, lang).await,
+                    ],
+                ),
+                false => tr(lang, "vote.failed", &[&tally.motion]),
+            })
+        }
+    };
+
+    {
+        let data = ctx.data.read().await;
+        let mut votes = data
+            .get::<Votes>()
+            .expect("Votes placed in at initialisation.")
+            .lock()
+            .await;
+
+        votes.running.remove(&guild_id);
+        if content.is_some() && !tally.passed() {
+            votes
+                .cooldowns
+                .insert((guild_id, user.id), Instant::now() + VOTE_COOLDOWN);
+        }
+    }
+
+    if let Some(content) = content {
+        check_msg(
+            cmd.create_followup_message(&ctx.http, |r| r.content(&content))
+                .await,
+        );
+    }
+
+    Ok(())
+}
+
+/// Carries `motion` out without asking anyone, `reason` being the catalog key
+/// saying why.
+pub async fn bypass(ctx: &Context, cmd: &Invocation<'_>, motion: Motion, reason: &str) -> Res {
+    let manager = songbird::get(ctx)
+        .await
+        .expect("Songbird Voice client placed in at initialisation.")
+        .clone();
+
+    let guild_id = cmd.guild_id().unwrap();
+    let lang = i18n::lang(ctx, Some(guild_id), cmd.locale()).await;
+    let content = match manager.get(guild_id) {
+        Some(handler_lock) => format!(
+            "{} ({} {})",
+            motion.carry(ctx, guild_id, &handler_lock, lang).await,
+            tr(lang, reason, &[]),
+            cmd.user().mention()
+        ),
+        None => tr(lang, "not_playing", &[]),
+    };
+
+    check_msg(
+        cmd.edit_original_interaction_response(&ctx.http, |response| response.content(content))
+            .await,
+    );
+
+    Ok(())
+}
+
+/// Collects votes until the time is up or the result is decided, returns
+/// whether the vote passed.
+async fn run(
+    ctx: &Context,
+    cmd: &Invocation<'_>,
+    voters: &[UserId],
+    tally: &mut Tally,
+    yeps: &mut UnboundedReceiver<(UserId, String)>,
+) -> bool {
+    check_msg(
+        cmd.edit_original_interaction_response(&ctx.http, |response| {
+            response
+                .create_embed(|e| tally.embed(e))
+                .components(|c| c.add_action_row(VoteBtn::action_row(tally.lang)))
+        })
+        .await,
+    );
+
+    let message = match cmd.get_interaction_response(&ctx.http).await {
+        Ok(message) => message,
+        Err(why) => {
+            println!("Err getting vote message: {:?}", why);
+            return false;
+        }
+    };
+    set_link(ctx, cmd.guild_id().unwrap(), message.link()).await;
+
+    let mut votes = message
+        .await_component_interactions(&ctx)
+        .timeout(tally.duration)
+        .await;
+
+    loop {
+        let (user_id, name, vote) = tokio::select! {
+            vote = votes.next() => match vote {
+                Some(vote) => (vote.user.id, vote.user.name.clone(), Some(vote)),
+                None => break,
+            },
+            Some((user_id, name)) = yeps.recv() => (user_id, name, None),
+        };
+
+        if !voters.contains(&user_id) {
+            continue;
+        }
+
+        // Votes are final, so that the vote can close as soon as it is decided.
+        if tally.yep.contains_key(&user_id) || tally.nope.contains_key(&user_id) {
+            if let Some(vote) = vote {
+                let lang = i18n::lang(ctx, vote.guild_id, &vote.locale).await;
+                check_msg(
+                    vote.create_interaction_response(&ctx.http, |response| {
+                        interaction_reply(response, tr(lang, "vote.already_voted", &[]), true)
+                    })
+                    .await,
+                );
+            }
+            continue;
+        }
+
+        match &vote {
+            Some(vote) if vote.data.custom_id != VoteBtn::Yep.custom_id() => {
+                tally.nope.insert(user_id, name)
+            }
+            _ => tally.yep.insert(user_id, name),
+        };
+
+        match vote {
+            Some(vote) => check_msg(
+                vote.create_interaction_response(&ctx.http, |response| {
+                    response
+                        .kind(InteractionResponseType::UpdateMessage)
+                        .interaction_response_data(|d| {
+                            d.create_embed(|e| tally.embed(e))
+                                .components(|c| c.add_action_row(VoteBtn::action_row(tally.lang)))
+                        })
+                })
+                .await,
+            ),
+            None => check_msg(
+                cmd.edit_original_interaction_response(&ctx.http, |response| {
+                    response.create_embed(|e| tally.embed(e))
+                })
+                .await,
+            ),
+        }
+
+        if tally.outcome().is_some() {
+            break;
+        }
+    }
+
+    check_msg(
+        cmd.edit_original_interaction_response(&ctx.http, |response| {
+            response
+                .components(|c| c.set_action_rows(Vec::new()))
+                .create_embed(|e| {
+                    tally
+                        .embed(e)
+                        .description(tr(tally.lang, "vote.ended", &[]))
+                })
+        })
+        .await,
+    );
+
+    tally.passed()
+}
+
+async fn set_link(ctx: &Context, guild_id: GuildId, link: String) {
+    let data = ctx.data.read().await;
+    let mut votes = data
+        .get::<Votes>()
+        .expect("Votes placed in at initialisation.")
+        .lock()
+        .await;
+
+    if let Some(running) = votes.running.get_mut(&guild_id) {
+        running.link = Some(link);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tally(rule: VoteRule, eligible: usize, yep: u64, nope: u64) -> Tally {
+        Tally {
+            lang: Lang::En,
+            initiator: String::new(),
+            motion: String::new(),
+            rule,
+            duration: Duration::from_secs(15),
+            eligible,
+            yep: (0..yep).map(|u| (UserId(u), String::new())).collect(),
+            nope: (yep..yep + nope)
+                .map(|u| (UserId(u), String::new()))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn majority_takes_more_yeps_than_nopes() {
+        assert!(tally(VoteRule::Majority, 5, 2, 1).passed());
+        assert!(!tally(VoteRule::Majority, 5, 1, 1).passed());
+        assert!(!tally(VoteRule::Majority, 5, 0, 0).passed());
+    }
+
+    #[test]
+    fn percent_counts_everyone_eligible() {
+        assert!(tally(VoteRule::Percent(50), 4, 2, 0).passed());
+        assert!(!tally(VoteRule::Percent(50), 4, 1, 0).passed());
+        assert!(!tally(VoteRule::Percent(0), 4, 0, 0).passed());
+    }
+
+    #[test]
+    fn count_is_capped_at_who_can_vote() {
+        assert!(tally(VoteRule::Count(3), 5, 3, 2).passed());
+        assert!(!tally(VoteRule::Count(3), 5, 2, 0).passed());
+        assert!(tally(VoteRule::Count(10), 2, 2, 0).passed());
+        assert!(!tally(VoteRule::Count(0), 2, 0, 0).passed());
+    }
+
+    #[test]
+    fn closes_once_the_rest_cannot_change_it() {
+        // 3 out of 5 is a majority whatever the other 2 say.
+        assert_eq!(tally(VoteRule::Majority, 5, 3, 0).outcome(), Some(true));
+        // Same the other way around.
+        assert_eq!(tally(VoteRule::Majority, 5, 0, 3).outcome(), Some(false));
+        assert_eq!(tally(VoteRule::Majority, 5, 2, 1).outcome(), None);
+
+        assert_eq!(tally(VoteRule::Percent(75), 4, 3, 0).outcome(), Some(true));
+        assert_eq!(tally(VoteRule::Percent(75), 4, 2, 2).outcome(), Some(false));
+        assert_eq!(tally(VoteRule::Percent(75), 4, 2, 0).outcome(), None);
+
+        assert_eq!(tally(VoteRule::Count(2), 4, 2, 0).outcome(), Some(true));
+        assert_eq!(tally(VoteRule::Count(2), 4, 1, 3).outcome(), Some(false));
+        assert_eq!(tally(VoteRule::Count(2), 4, 1, 0).outcome(), None);
+    }
+
+    #[test]
+    fn a_lone_listener_decides_alone() {
+        assert_eq!(tally(VoteRule::Majority, 1, 1, 0).outcome(), Some(true));
+        assert_eq!(tally(VoteRule::Majority, 1, 0, 0).outcome(), None);
+    }
+
+    #[test]
+    fn deafened_members_do_not_vote() {
+        let listeners = [UserId(1), UserId(2), UserId(3)];
+        assert_eq!(
+            hearing(&listeners, &[UserId(2), UserId(4)]),
+            vec![UserId(1), UserId(3)]
+        );
+        assert_eq!(hearing(&listeners, &[]), listeners.to_vec());
+    }
+}
@@ -0,0 +1,48 @@
+use serde::Deserialize;
+use tokio::process::Command;
+
+#[derive(Deserialize)]
+struct Playlist {
+    #[serde(default)]
+    entries: Vec<Entry>,
+}
+
+#[derive(Deserialize)]
+pub struct Entry {
+    pub id: String,
+    url: Option<String>,
+}
+
+impl Entry {
+    pub fn url(&self) -> String {
+        match &self.url {
+            Some(url) if url.starts_with("http") => url.clone(),
+            _ => format!("https://www.youtube.com/watch?v={}", self.id),
+        }
+    }
+}
+
+/// Lists up to `limit` entries of a playlist without resolving each of them.
+pub async fn flat_playlist(url: &str, limit: usize) -> Vec<Entry> {
+    let output = match Command::new("yt-dlp")
+        .args(["--flat-playlist", "--dump-single-json", "--playlist-end"])
+        .arg(limit.to_string())
+        .arg(url)
+        .output()
+        .await
+    {
+        Ok(output) => output,
+        Err(why) => {
+            println!("Err running yt-dlp: {:?}", why);
+            return Vec::new();
+        }
+    };
+
+    match serde_json::from_slice::<Playlist>(&output.stdout) {
+        Ok(playlist) => playlist.entries,
+        Err(why) => {
+            println!("Err parsing yt-dlp playlist: {:?}", why);
+            Vec::new()
+        }
+    }
+}